@@ -2,9 +2,29 @@
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+/// Interface other contracts can use to verify the ownership and attestation of a property
+/// (e.g a lending contract checking collateral) without hardcoding selectors
+#[ink::trait_definition]
+pub trait PropertyRegistry {
+    /// Return the account that currently owns a property, or `None` if the property does not exist
+    #[ink(message)]
+    fn owner_of(&self, property_id: Vec<u8>) -> Option<AccountId>;
+
+    /// Return whether a property exists and has been attested
+    #[ink(message)]
+    fn is_attested(&self, property_id: Vec<u8>) -> bool;
+}
+
 #[ink::contract]
 mod delphi {
-    use ink::storage::Mapping;
+    use super::PropertyRegistry;
+    use ink::env::hash::Blake2x256;
+    use ink::prelude::string::ToString;
+    use ink::storage::{Lazy, Mapping};
+    use scale::Encode;
     use scale_info::prelude::vec;
     use scale_info::prelude::vec::Vec;
 
@@ -35,10 +55,26 @@ mod delphi {
         property_claim_addr: PropertyClaimAddr,
         /// Type the property belongs to.
         property_type_id: PropertyTypeId,
-        /// List of previous owners and time of transfer
-        transfer_history: Vec<(AccountId, PropertyTransferTimestamp)>,
         /// The time and the account that made the assertion
         assertion: (AssertionTimestamp, AccountId),
+        /// The accounts that have signed the property document so far and when they signed
+        signatures: Vec<(AccountId, AssertionTimestamp)>,
+        /// Whether the property is subject to an unresolved legal dispute
+        disputed: bool,
+        /// The notary or registrar office that processed the claim
+        office: Office,
+        /// Structured on-chain attributes of the property, e.g ("zoning", "residential")
+        attributes: Vec<(AttributeKey, AttributeValue)>,
+        /// Whether the property's documents were digitized from paper records
+        digitized: bool,
+        /// The authority that recorded the digitization
+        digitized_by: AccountId,
+        /// The block at which the claim was registered
+        claimed_at: BlockNumber,
+        /// The share of the original property this property represents, in basis points
+        share_bps: u16,
+        /// The block at which the property record was last changed
+        last_modified_block: BlockNumber,
     }
 
     /// The struct describing a property type
@@ -51,21 +87,176 @@ mod delphi {
         /// Id of property type
         id: PropertyTypeId,
         address: PropertyRequirementAddr,
+        /// Number of signatures a property of this type needs before it is fully attested
+        required_signatures: u8,
+        /// Fee a property of this type costs to transfer, paid to the registrar of the type
+        transfer_fee: Balance,
     }
 
-    /// Delphi's error type.
+    /// The struct describing a mortgage held on a property
+    #[derive(scale::Decode, scale::Encode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Mortgage {
+        /// Account that lent against the property
+        lender: AccountId,
+        /// Amount lent
+        principal: Balance,
+        /// Amount repaid so far
+        repaid: Balance,
+        /// IPFS address (CID) of the mortgage deed
+        doc_ipfs: Vec<u8>,
+    }
+
+    /// The flags and counters describing the state of a property
+    #[derive(scale::Decode, scale::Encode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct PropertyMeta {
+        /// Whether the property is subject to an unresolved legal dispute
+        disputed: bool,
+        /// Whether a mortgage or government charge blocks the transfer of the property
+        encumbered: bool,
+        /// Whether the property has been attested
+        attested: bool,
+        /// Number of times the property changed hands
+        transfer_count: u32,
+        /// Number of outstanding government charges on the property
+        charge_count: u32,
+        /// The block at which the claim was registered
+        claimed_at: BlockNumber,
+        /// The block at which the property record was last changed
+        last_modified_block: BlockNumber,
+    }
+
+    /// The chain of custody and attestation of a property
+    #[derive(scale::Decode, scale::Encode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct VerificationReport {
+        /// Every account that has owned the property, oldest first and ending with the current owner
+        owners: Vec<AccountId>,
+        /// The time each owner transferred the property to the next one
+        transfer_times: Vec<TimeString>,
+        /// The authority that attested the property
+        attester: AccountId,
+        /// The time the property was attested, empty if it is unattested
+        attested_at: AssertionTimestamp,
+    }
+
+    /// The manner in which a property changed hands
     #[derive(scale::Decode, scale::Encode, Clone)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
+    pub enum TransferKind {
+        /// The property was transferred as a whole and kept its id
+        Whole,
+        /// The property was split into two new properties, one for each party
+        Split {
+            senders_property_id: PropertyId,
+            recipients_property_id: PropertyId,
+        },
+    }
+
+    /// Delphi's error type.
+    #[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
     pub enum Error {
         /// Returned when a property owner tries to transfer to himself
         CannotTransferToSelf,
-        /// Returned when an unauthorized account tries to sign a property document (attestation)
-        UnauthorizedAccount,
+        /// Returned when an unauthorized account tries to sign a property document (attestation).
+        /// It carries the account that was refused
+        UnauthorizedAccount(AccountId),
+        /// Returned when an IPFS address does not look like a valid CID
+        InvalidCid,
+        /// Returned when a property type cannot be removed because claims are still filed under it
+        TypeHasActiveClaims,
+        /// Returned when the property being referenced does not exist.
+        /// It carries the id of the missing property
+        PropertyNotFound(PropertyId),
+        /// Returned when an account other than the property owner tries to act on a property
+        NotPropertyOwner,
+        /// Returned when trying to change a property that has already been attested
+        AlreadyAttested,
+        /// Returned when the caller has not registered an account
+        AccountNotFound,
+        /// Returned when trying to transfer or attest a property that is under dispute
+        PropertyDisputed,
+        /// Returned when splitting a property whose subdivision plan has not been approved
+        SubdivisionNotApproved,
+        /// Returned when trying to transfer a property that has outstanding charges against it
+        PropertyEncumbered,
+        /// Returned when an account other than the contract owner calls an owner-only message
+        NotOwner,
+        /// Returned when the value sent with a call does not cover the required fee
+        InsufficientFee,
+        /// Returned when a batch holds more than `MAX_BATCH_SIZE` entries
+        BatchTooLarge,
+        /// Returned when a property already carries `MAX_ATTRIBUTES` attributes
+        TooManyAttributes,
+        /// Returned when a claimer tries to peer review their own claim
+        CannotReviewOwn,
+        /// Returned when an account name is empty or longer than `MAX_NAME_LENGTH` bytes
+        InvalidNameLength,
+        /// Returned when an empty property id or property type id is given
+        EmptyPropertyId,
+        /// Returned when a property has no active mortgage
+        NoMortgage,
+        /// Returned when sending funds out of the contract fails
+        TransferFailed,
+        /// Returned when there is no recent transfer of the property that the caller can reverse
+        NoReversibleTransfer,
+        /// Returned when the window to reverse a transfer has passed
+        GracePeriodElapsed,
+        /// Returned when the claimer of a property tries to attest it
+        SelfAttestationForbidden,
+        /// Returned when a property type id is already registered by an authority
+        TypeAlreadyRegistered,
+        /// Returned when a record from an external registry has already been imported
+        DuplicateExternalId,
+        /// Returned when the contract code could not be replaced
+        SetCodeFailed,
+        /// Returned when properties of different types are merged
+        MixedPropertyTypes,
+        /// Returned when the property type being referenced is not registered
+        UnknownPropertyType,
+        /// Returned when a property is transferred to an account that has not registered
+        RecipientNotRegistered,
+        /// Returned when the shares of a split do not add up to the share of the property being split
+        InvalidShareSplit,
+        /// Returned when a property is signed as a type it is not filed under
+        TypeMismatch,
+        /// Returned when an account that still owns properties tries to remove itself
+        AccountHasProperties,
+        /// Returned when a claim is filed under a property id that is already on record.
+        /// It carries the id of the existing property
+        PropertyAlreadyExists(PropertyId),
+        /// Returned when a property document is signed without an assertion timestamp
+        EmptyAssertionTimestamp,
     }
 
+    /// The maximum number of entries a batch message accepts
+    const MAX_BATCH_SIZE: usize = 256;
+    /// The maximum number of attributes a property can carry
+    const MAX_ATTRIBUTES: usize = 32;
+    /// The maximum length in bytes of an account name
+    const MAX_NAME_LENGTH: usize = 64;
+    /// The share of a property, in basis points, that a whole (unsplit) property represents
+    const FULL_SHARE_BPS: u16 = 10_000;
+    /// The version of the storage schema this code expects
+    const CONTRACT_VERSION: u32 = 1;
+
     /// Delphi's result type.
     pub type Result<T> = core::result::Result<T, Error>;
     /// The id of the property
@@ -80,10 +271,53 @@ mod delphi {
     type PropertyClaimAddr = Vec<u8>;
     /// The Unix timestamp recording the time a property transfer was made
     type PropertyTransferTimestamp = TimeString;
+    /// A previous owner of a property, the time it transferred the property, the hash of the sale contract
+    /// and the IPFS address of the transfer document
+    type TransferRecord = (
+        AccountId,
+        PropertyTransferTimestamp,
+        SaleContractHash,
+        TransferDocAddr,
+    );
+    /// The IPFS address (CID) of the legal instrument (e.g deed or court order) behind a transfer.
+    /// It is empty when no document was recorded
+    type TransferDocAddr = Vec<u8>;
+    /// The previous owner, block and claim document of a whole transfer that can still be reversed
+    type ReversibleTransfer = (AccountId, BlockNumber, PropertyClaimAddr);
+    /// The hash of the sale contract behind a whole transfer. The zero hash means no contract was attached
+    type SaleContractHash = [u8; 32];
+    /// The id of a property in the registry it was migrated from
+    type ExternalRegistryId = Vec<u8>;
     /// The time the assertion was made by the right authority after verifying that the property belongs to the account
     type AssertionTimestamp = Vec<u8>;
+    /// The identifier of a notary or registrar office
+    type Office = Vec<u8>;
+    /// The key of a property attribute, e.g "area_sqm"
+    type AttributeKey = Vec<u8>;
+    /// The value of a property attribute, e.g "450"
+    type AttributeValue = Vec<u8>;
     /// The (JS) parsable AccountId in vector form
     type AccountIdVec = Vec<u8>;
+    /// The IPFS address (CID) of the document stating the grounds of a dispute
+    type DisputeReasonAddr = Vec<u8>;
+    /// The IPFS address (CID) of an approved subdivision plan
+    type SubdivisionPlanAddr = Vec<u8>;
+    /// The reference of a charge levied by the government (e.g unpaid rates)
+    type ChargeRef = Vec<u8>;
+    /// A government charge and the amount owed
+    type GovernmentCharge = (ChargeRef, Balance);
+    /// The IPFS address (CID) of a peer reviewer's notes
+    type ReviewNotesAddr = Vec<u8>;
+    /// A peer review: the reviewer, whether it approved the claim and its notes
+    type PeerReview = (AccountId, bool, ReviewNotesAddr);
+    /// The IPFS address (CID) of an insurance policy document
+    type PolicyAddr = Vec<u8>;
+    /// An insurance policy and the block at which it expires
+    type Policy = (PolicyAddr, BlockNumber);
+    /// The IPFS address (CID) of the document setting out the terms of an easement
+    type EasementTermsAddr = Vec<u8>;
+    /// An access right over a property held by a beneficiary, and its terms
+    type Easement = (AccountId, EasementTermsAddr);
 
     //// Event to announce the creation of an account
     #[ink(event)]
@@ -93,6 +327,21 @@ mod delphi {
         name: Vec<u8>,
     }
 
+    /// Event to announce that an account removed itself
+    #[ink(event)]
+    pub struct AccountRemoved {
+        #[ink(topic)]
+        account_id: AccountId,
+    }
+
+    /// Event to announce that an account changed its name
+    #[ink(event)]
+    pub struct AccountRenamed {
+        #[ink(topic)]
+        account_id: AccountId,
+        name: Vec<u8>,
+    }
+
     //// Event to announce the registration of a property type
     #[ink(event)]
     pub struct PropertyTypeRegistered {
@@ -102,6 +351,14 @@ mod delphi {
         ptype_ipfs_addr: PropertyRequirementAddr,
     }
 
+    /// Event to announce the removal of a property type
+    #[ink(event)]
+    pub struct PropertyTypeDeregistered {
+        #[ink(topic)]
+        account_id: AccountId,
+        property_type_id: PropertyTypeId,
+    }
+
     //// Event to announce the registration of a claim to a property
     #[ink(event)]
     pub struct PropertyClaimRegistered {
@@ -112,6 +369,16 @@ mod delphi {
         property_id: PropertyId,
     }
 
+    /// Event to announce that the claim document of a property was replaced
+    #[ink(event)]
+    pub struct ClaimAddressUpdated {
+        #[ink(topic)]
+        claimer: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+        claim_ipfs_addr: PropertyClaimAddr,
+    }
+
     /// Event to announce the successful transfer of a property
     #[ink(event)]
     pub struct PropertyTransferred {
@@ -121,6 +388,7 @@ mod delphi {
         recipient: AccountId,
         #[ink(topic)]
         property_id: PropertyId,
+        kind: TransferKind,
     }
 
     /// Event to announce the successful attestation of a property
@@ -132,15 +400,294 @@ mod delphi {
         property_id: PropertyId,
     }
 
+    /// Event to announce that an insurance policy was recorded against a property
+    #[ink(event)]
+    pub struct PolicyAdded {
+        #[ink(topic)]
+        property_id: PropertyId,
+        policy_ipfs_addr: PolicyAddr,
+        expiry_block: BlockNumber,
+    }
+
+    /// Event to announce that a property has gathered all the signatures its type requires
+    #[ink(event)]
+    pub struct PropertyFullyAttested {
+        #[ink(topic)]
+        property_id: PropertyId,
+        assertion_timestamp: AssertionTimestamp,
+    }
+
+    /// Event to announce that a property's official map reference was set
+    #[ink(event)]
+    pub struct MapReferenceSet {
+        #[ink(topic)]
+        authority: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+        map_ref: Vec<u8>,
+    }
+
+    /// Event to announce that a property has been disputed
+    #[ink(event)]
+    pub struct DisputeRaised {
+        #[ink(topic)]
+        disputant: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+        reason_ipfs_addr: DisputeReasonAddr,
+    }
+
+    /// Event to announce that the dispute on a property has been resolved
+    #[ink(event)]
+    pub struct DisputeResolved {
+        #[ink(topic)]
+        authority: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+    }
+
+    /// Event to announce that an authority approved the subdivision of a property
+    #[ink(event)]
+    pub struct SubdivisionApproved {
+        #[ink(topic)]
+        authority: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+        plan_ipfs_addr: SubdivisionPlanAddr,
+    }
+
+    /// Event to announce that a government charge was recorded against a property
+    #[ink(event)]
+    pub struct GovernmentChargeAdded {
+        #[ink(topic)]
+        authority: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+        charge_ref: ChargeRef,
+        amount: Balance,
+    }
+
+    /// Event to announce that a government charge on a property was cleared
+    #[ink(event)]
+    pub struct GovernmentChargeCleared {
+        #[ink(topic)]
+        authority: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+        charge_ref: ChargeRef,
+    }
+
+    /// Event to announce that the collected fees were withdrawn from the contract
+    #[ink(event)]
+    pub struct FeesWithdrawn {
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    /// Event to announce that an attribute of a property was set
+    #[ink(event)]
+    pub struct AttributeSet {
+        #[ink(topic)]
+        property_id: PropertyId,
+        key: AttributeKey,
+        value: AttributeValue,
+    }
+
+    /// Event to announce that a registrar delegated attestation of a property type to a deputy
+    #[ink(event)]
+    pub struct SignerAdded {
+        #[ink(topic)]
+        registrar: AccountId,
+        #[ink(topic)]
+        deputy: AccountId,
+        property_type_id: PropertyTypeId,
+    }
+
+    /// Event to announce that a registrar revoked a deputy's attestation authority
+    #[ink(event)]
+    pub struct SignerRemoved {
+        #[ink(topic)]
+        registrar: AccountId,
+        #[ink(topic)]
+        deputy: AccountId,
+        property_type_id: PropertyTypeId,
+    }
+
+    /// Event to announce that a claim was peer reviewed
+    #[ink(event)]
+    pub struct PeerReviewed {
+        #[ink(topic)]
+        reviewer: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+        approve: bool,
+        notes_ipfs_addr: ReviewNotesAddr,
+    }
+
+    /// Event to announce that a mortgage was taken on a property
+    #[ink(event)]
+    pub struct MortgageCreated {
+        #[ink(topic)]
+        lender: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+        principal: Balance,
+    }
+
+    /// Event to announce a repayment towards the mortgage on a property
+    #[ink(event)]
+    pub struct MortgageRepaid {
+        #[ink(topic)]
+        payer: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+        amount: Balance,
+        repaid: Balance,
+    }
+
+    /// Event to announce that the mortgage on a property has been paid off and released
+    #[ink(event)]
+    pub struct MortgageReleased {
+        #[ink(topic)]
+        lender: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+    }
+
+    /// Event to announce that the previous owner of a property reversed its transfer
+    #[ink(event)]
+    pub struct TransferReversed {
+        #[ink(topic)]
+        previous_owner: AccountId,
+        #[ink(topic)]
+        reverted_owner: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+    }
+
+    /// Event to announce that the owner of a property granted an easement over it
+    #[ink(event)]
+    pub struct EasementGranted {
+        #[ink(topic)]
+        property_id: PropertyId,
+        #[ink(topic)]
+        beneficiary: AccountId,
+        terms_ipfs_addr: EasementTermsAddr,
+    }
+
+    /// Event to announce that an authority recorded a property's documents as digitized from paper
+    #[ink(event)]
+    pub struct PropertyDigitized {
+        #[ink(topic)]
+        authority: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+    }
+
+    /// Event to announce that the contract owner removed a claim as fraudulent
+    #[ink(event)]
+    pub struct ClaimForceRevoked {
+        #[ink(topic)]
+        admin: AccountId,
+        #[ink(topic)]
+        claimer: AccountId,
+        #[ink(topic)]
+        property_id: PropertyId,
+    }
+
+    /// Event to announce that the contract storage was migrated to a new schema version
+    #[ink(event)]
+    pub struct Migrated {
+        from: u32,
+        to: u32,
+    }
+
+    /// Event to announce that several properties were merged into one
+    #[ink(event)]
+    pub struct PropertiesMerged {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        merged_property_id: PropertyId,
+        merged_from: Vec<PropertyId>,
+    }
+
+    /// Event to announce that a claim was moved to a different property type
+    #[ink(event)]
+    pub struct PropertyReclassified {
+        #[ink(topic)]
+        property_id: PropertyId,
+        old_property_type_id: PropertyTypeId,
+        #[ink(topic)]
+        new_property_type_id: PropertyTypeId,
+    }
+
+    /// The contract storage.
+    /// It must stay compatible across `set_code` upgrades: the plain (non-`Mapping`) fields are stored together
+    /// in declaration order, so they are append-only and new ones go after `schema_version`.
+    /// `Mapping` fields are keyed by their name, so they must never be renamed or change their key or value types
     #[ink(storage)]
     pub struct Delphi {
+        /// The account that deployed the contract and administers it
+        owner: AccountId,
         accounts: Mapping<AccountId, AccountInfo>,
         registrations: Mapping<AccountId, Vec<PropertyType>>,
+        /// The original registrar of each property type
+        type_owner: Mapping<PropertyTypeId, AccountId>,
+        /// Every registered property type id, without duplicates.
+        /// It is kept in its own storage cell so that it is not loaded with the rest of the contract on every call
+        all_type_ids: Lazy<Vec<PropertyTypeId>>,
+        /// Deputies a registrar has authorized to sign documents of a property type on its behalf
+        delegated_signers: Mapping<PropertyTypeId, Vec<AccountId>>,
         claims: Mapping<PropertyTypeId, Vec<PropertyId>>,
         properties: Mapping<PropertyId, Property>,
+        /// List of previous owners and time of transfer of each property.
+        /// It is kept out of `Property` so that loading a property costs the same however often it changed hands
+        transfer_histories: Mapping<PropertyId, Vec<TransferRecord>>,
         /// This Mapping field is simply unnecessary. But due to the fact that we've found it difficult to
         /// decode an AccountId with Javascript, we will be returning a vec instead of an accountId
         account_ids: Mapping<AccountId, AccountIdVec>,
+        /// The properties each authority has signed
+        attested_index: Mapping<AccountId, Vec<PropertyId>>,
+        /// Number of blocks after a whole transfer during which the previous owner can reverse it, per property type
+        transfer_grace_blocks: Mapping<PropertyTypeId, BlockNumber>,
+        /// The latest whole transfer of a property, kept while it can still be reversed
+        reversible_transfers: Mapping<PropertyId, ReversibleTransfer>,
+        /// The property each imported external registry id was migrated to
+        external_ids: Mapping<ExternalRegistryId, PropertyId>,
+        /// The external registry id each imported property was migrated from
+        imported_from: Mapping<PropertyId, ExternalRegistryId>,
+        /// The properties each account currently owns
+        owned_properties: Mapping<AccountId, Vec<PropertyId>>,
+        /// Insurance policies held on a property and the block at which each expires
+        policies: Mapping<PropertyId, Vec<Policy>>,
+        /// Access rights (such as a right of way) granted over a property
+        easements: Mapping<PropertyId, Vec<Easement>>,
+        /// The official map tile or sheet reference of a property, for GIS integration
+        map_refs: Mapping<PropertyId, Vec<u8>>,
+        /// The account that raised the open dispute on a property and the grounds for it
+        disputes: Mapping<PropertyId, (AccountId, DisputeReasonAddr)>,
+        /// Subdivision plans approved by an authority, required before a property can be split
+        subdivisions: Mapping<PropertyId, SubdivisionPlanAddr>,
+        /// Outstanding government charges on a property. A property with charges cannot be transferred
+        government_charges: Mapping<PropertyId, Vec<GovernmentCharge>>,
+        /// Reviews of a claim by registered accounts other than the claimer
+        peer_reviews: Mapping<PropertyId, Vec<PeerReview>>,
+        /// Active mortgages on a property. A mortgaged property cannot be transferred
+        mortgages: Mapping<PropertyId, Mortgage>,
+        /// Number of registered accounts
+        total_accounts: u32,
+        /// Number of properties on record
+        total_properties: u32,
+        /// Number of claims registered across all property types
+        total_claims: u64,
+        /// Minimum value that must be sent with `register_ptype`
+        ptype_fee: Balance,
+        /// Fees collected and not yet withdrawn by the owner
+        collected_fees: Balance,
+        /// The version of the schema the storage is laid out in
+        schema_version: u32,
     }
 
     impl Delphi {
@@ -148,11 +695,36 @@ mod delphi {
         #[ink(constructor)]
         pub fn new() -> Self {
             Delphi {
+                owner: Self::env().caller(),
                 accounts: Default::default(),
                 registrations: Default::default(),
+                type_owner: Default::default(),
+                all_type_ids: Default::default(),
+                delegated_signers: Default::default(),
                 claims: Default::default(),
                 properties: Default::default(),
+                transfer_histories: Default::default(),
                 account_ids: Default::default(),
+                attested_index: Default::default(),
+                transfer_grace_blocks: Default::default(),
+                reversible_transfers: Default::default(),
+                external_ids: Default::default(),
+                imported_from: Default::default(),
+                owned_properties: Default::default(),
+                policies: Default::default(),
+                easements: Default::default(),
+                map_refs: Default::default(),
+                disputes: Default::default(),
+                subdivisions: Default::default(),
+                government_charges: Default::default(),
+                peer_reviews: Default::default(),
+                mortgages: Default::default(),
+                total_accounts: 0,
+                total_properties: 0,
+                total_claims: 0,
+                ptype_fee: 0,
+                collected_fees: 0,
+                schema_version: CONTRACT_VERSION,
             }
         }
 
@@ -167,11 +739,20 @@ mod delphi {
             // Get the contract caller
             let caller = Self::env().caller();
 
+            if !Self::is_valid_name(&name) {
+                return Err(Error::InvalidNameLength);
+            }
+
             let new_account = AccountInfo {
                 name: name.clone(),
                 timestamp,
             };
 
+            // only count accounts registering for the first time
+            if !self.accounts.contains(caller) {
+                self.total_accounts = self.total_accounts.saturating_add(1);
+            }
+
             // Insert into storage
             self.accounts.insert(&caller, &new_account);
 
@@ -187,9 +768,63 @@ mod delphi {
             Ok(())
         }
 
+        /// Change the name of the caller's account, leaving the rest of the account untouched
+        #[ink(message, payable)]
+        pub fn rename_account(&mut self, new_name: Vec<u8>) -> Result<()> {
+            // Get the contract caller
+            let caller = Self::env().caller();
+
+            if !Self::is_valid_name(&new_name) {
+                return Err(Error::InvalidNameLength);
+            }
+
+            let mut info = self.accounts.get(caller).ok_or(Error::AccountNotFound)?;
+            info.name = new_name.clone();
+            self.accounts.insert(caller, &info);
+
+            // Emit event
+            self.env().emit_event(AccountRenamed {
+                account_id: caller,
+                name: new_name,
+            });
+
+            Ok(())
+        }
+
+        /// Remove the caller's account.
+        /// An account can only be removed once it owns no properties, so that no property is left pointing to it
+        #[ink(message, payable)]
+        pub fn remove_account(&mut self) -> Result<()> {
+            // Get the contract caller
+            let caller = Self::env().caller();
+
+            if !self.accounts.contains(caller) {
+                return Err(Error::AccountNotFound);
+            }
+
+            if !self
+                .owned_properties
+                .get(caller)
+                .unwrap_or_default()
+                .is_empty()
+            {
+                return Err(Error::AccountHasProperties);
+            }
+
+            self.accounts.remove(caller);
+            self.account_ids.remove(caller);
+            self.owned_properties.remove(caller);
+            self.total_accounts = self.total_accounts.saturating_sub(1);
+
+            // Emit event
+            self.env().emit_event(AccountRemoved { account_id: caller });
+
+            Ok(())
+        }
+
         /// Check if an account exists.
         /// It also returns the name of the user if it exists
-        #[ink(message, payable)]
+        #[ink(message)]
         pub fn account_exists(&self) -> (bool, Vec<u8>) {
             // get the contract caller
             let caller = Self::env().caller();
@@ -200,21 +835,92 @@ mod delphi {
             }
         }
 
+        /// Return the name of any registered account, or `None` if the account is unregistered
+        #[ink(message)]
+        pub fn account_name(&self, account_id: AccountId) -> Option<Vec<u8>> {
+            self.accounts.get(account_id).map(|info| info.name)
+        }
+
+        /// Return whether an account has both its account info and its parsable account id on record.
+        /// Monitoring tools can use it to flag accounts where the two have drifted apart
+        #[ink(message)]
+        pub fn check_account_consistency(&self, account_id: AccountId) -> bool {
+            self.accounts.contains(account_id) && self.account_ids.contains(account_id)
+        }
+
+        /// Return the time any registered account was created, or `None` if the account is unregistered
+        #[ink(message)]
+        pub fn account_created_at(&self, account_id: AccountId) -> Option<TimeString> {
+            self.accounts.get(account_id).map(|info| info.timestamp)
+        }
+
+        /// Return the number of registered accounts
+        #[ink(message)]
+        pub fn total_accounts(&self) -> u32 {
+            self.total_accounts
+        }
+
+        /// Return the number of properties on record
+        #[ink(message)]
+        pub fn total_properties(&self) -> u32 {
+            self.total_properties
+        }
+
+        /// Return the number of claims registered across all property types
+        #[ink(message)]
+        pub fn total_claims(&self) -> u64 {
+            self.total_claims
+        }
+
+        /// Return the SCALE encoded `AccountInfo` of an account, for clients that decode it themselves.
+        /// `None` is returned if the account is unregistered
+        #[ink(message)]
+        pub fn raw_account_info(&self, account_id: AccountId) -> Option<Vec<u8>> {
+            self.accounts.get(account_id).map(|info| info.encode())
+        }
+
         /// Register a property type.
-        /// This should only be called by an authority figure (e.g Ministry of Lands)
+        /// This should only be called by an authority figure (e.g Ministry of Lands).
+        /// The value sent with the call must cover the registration fee set by the owner.
+        /// `required_signatures` is the number of authorities that must sign a property of this type before it is fully attested.
+        /// `transfer_fee` is what a transfer of a property of this type costs, paid to the registrar. It can be zero
         #[ink(message, payable)]
         pub fn register_ptype(
             &mut self,
             property_type_id: PropertyTypeId,
             ptype_ipfs_addr: PropertyRequirementAddr,
+            required_signatures: u8,
+            transfer_fee: Balance,
         ) -> Result<()> {
             // Get the contract caller
             let caller = Self::env().caller();
 
+            if property_type_id.is_empty() {
+                return Err(Error::EmptyPropertyId);
+            }
+
+            // make sure the registration fee is paid
+            let fee = self.env().transferred_value();
+            if fee < self.ptype_fee {
+                return Err(Error::InsufficientFee);
+            }
+
+            // make sure the requirements document can be resolved
+            if !Self::is_plausible_cid(&ptype_ipfs_addr) {
+                return Err(Error::InvalidCid);
+            }
+
+            // a type id names exactly one authority, so it can only be registered once
+            if self.type_owner.contains(&property_type_id) {
+                return Err(Error::TypeAlreadyRegistered);
+            }
+
             // create type
             let property_type = PropertyType {
                 id: property_type_id.clone(),
                 address: ptype_ipfs_addr.clone(),
+                required_signatures,
+                transfer_fee,
             };
 
             // Record the registrar.
@@ -229,6 +935,18 @@ mod delphi {
                 self.registrations.insert(caller, &property_types);
             }
 
+            // the account registering a type is its original registrar
+            self.type_owner.insert(&property_type_id, &caller);
+
+            let mut all_type_ids = self.all_type_ids.get_or_default();
+            if !all_type_ids.contains(&property_type_id) {
+                all_type_ids.push(property_type_id.clone());
+                self.all_type_ids.set(&all_type_ids);
+            }
+
+            // keep the fee for the owner to withdraw
+            self.collected_fees = self.collected_fees.saturating_add(fee);
+
             // Emit event
             self.env().emit_event(PropertyTypeRegistered {
                 account_id: caller,
@@ -239,23 +957,111 @@ mod delphi {
             Ok(())
         }
 
-        /// Return the info about property type documents created by a certain authority.
-        /// They are returned as concatenated bytes separated by the '###' character.
-        /// The property id and address are separated by a '~' character
-        /// E.g prop_id1~prop_addr1###prop_id2~prop_addr2
+        /// Remove a property type.
+        /// Only the authority that registered the type can remove it.
+        /// A type cannot be removed while claims are still filed under it, as those claims could never be attested again
         #[ink(message, payable)]
+        pub fn deregister_ptype(&mut self, property_type_id: PropertyTypeId) -> Result<()> {
+            // Get the contract caller
+            let caller = Self::env().caller();
+
+            let mut property_types = self.registrations.get(caller).unwrap_or_default();
+            if !property_types
+                .iter()
+                .any(|ptype| ptype.id == property_type_id)
+            {
+                // error! the caller did not register this type
+                return Err(Error::UnauthorizedAccount(caller));
+            }
+
+            // make sure no claims depend on the type
+            if self
+                .claims
+                .get(&property_type_id)
+                .is_some_and(|property_ids| !property_ids.is_empty())
+            {
+                return Err(Error::TypeHasActiveClaims);
+            }
+
+            // remove from the list of registered property types
+            property_types.retain(|ptype| ptype.id != property_type_id);
+            self.registrations.insert(caller, &property_types);
+
+            // the type's deputies and settings go along with the original registrar
+            if self.type_owner.get(&property_type_id) == Some(caller) {
+                self.type_owner.remove(&property_type_id);
+                self.delegated_signers.remove(&property_type_id);
+                self.transfer_grace_blocks.remove(&property_type_id);
+                let mut all_type_ids = self.all_type_ids.get_or_default();
+                all_type_ids.retain(|id| id != &property_type_id);
+                self.all_type_ids.set(&all_type_ids);
+            }
+
+            // Emit event
+            self.env().emit_event(PropertyTypeDeregistered {
+                account_id: caller,
+                property_type_id,
+            });
+
+            Ok(())
+        }
+
+        /// Return the property types registered by a certain authority.
+        /// This is the canonical typed alternative to `ptype_documents`
+        #[ink(message)]
+        pub fn registrations_of(&self, account_id: AccountId) -> Vec<PropertyType> {
+            self.registrations.get(account_id).unwrap_or_default()
+        }
+
+        /// Return the requirements document address of a property type, as set by its original registrar.
+        /// `None` is returned if the type is not registered
+        #[ink(message)]
+        pub fn requirement_addr(
+            &self,
+            property_type_id: PropertyTypeId,
+        ) -> Option<PropertyRequirementAddr> {
+            self.property_type(&property_type_id)
+                .map(|ptype| ptype.address)
+        }
+
+        /// Return every property type registered in the system, in the order they were first registered
+        #[ink(message)]
+        pub fn all_property_types(&self) -> Vec<PropertyType> {
+            self.all_property_types_paged(0, u32::MAX)
+        }
+
+        /// Paged variant of `all_property_types`.
+        /// It returns at most `limit` types, starting from the type at index `start`
+        #[ink(message)]
+        pub fn all_property_types_paged(&self, start: u32, limit: u32) -> Vec<PropertyType> {
+            self.all_type_ids
+                .get_or_default()
+                .iter()
+                .skip(start as usize)
+                .take(limit as usize)
+                .filter_map(|property_type_id| self.property_type(property_type_id))
+                .collect()
+        }
+
+        /// Return the info about property type documents created by a certain authority.
+        /// They are returned as concatenated bytes separated by the '###' character.
+        /// The property id and address are separated by a '~' character
+        /// E.g prop_id1~prop_addr1###prop_id2~prop_addr2
+        /// Any `~`, `#` or `\` inside an id or address is escaped with a preceding `\`.
+        /// To decode, split on the unescaped separators, then replace each `\x` with `x`
+        #[ink(message)]
         pub fn ptype_documents(&self, account_id: AccountId) -> Vec<u8> {
             if let Some(property_types) = self.registrations.get(&account_id) {
                 property_types
-                    .clone()
-                    .iter_mut()
+                    .iter()
                     .flat_map(|ptype| {
                         // make the `id` the collator
-                        ptype.id.push(b'~');
-                        ptype.id.extend(ptype.address.iter());
+                        let mut entry = Self::escape(&ptype.id);
+                        entry.push(b'~');
+                        entry.extend(Self::escape(&ptype.address));
 
-                        ptype.id.extend("###".as_bytes()); // add separator
-                        ptype.id.clone().into_iter()
+                        entry.extend("###".as_bytes()); // add separator
+                        entry.into_iter()
                     })
                     .collect()
             } else {
@@ -264,289 +1070,4358 @@ mod delphi {
         }
 
         /// Submit a claim to a particular property.
-        /// This is the first step, preceeding verification and attestation
+        /// This is the first step, preceeding verification and attestation.
+        /// `office` identifies the notary or registrar office that processed the claim
         #[ink(message, payable)]
         pub fn register_claim(
             &mut self,
             property_type_id: PropertyTypeId,
             property_id: PropertyId,
             claim_ipfs_addr: PropertyClaimAddr,
+            office: Office,
         ) -> Result<()> {
             // get claimer
             let claimer = Self::env().caller();
 
-            // create a new property document
-            let property = Property {
-                claimer: claimer.clone(),
-                property_claim_addr: claim_ipfs_addr,
-                property_type_id: property_type_id.clone(),
-                transfer_history: Vec::new(),
-                // the claimer's address is the default value for the id of the asserting authority
-                // this is not a bug as the assertion flag will be the timestamp of the signing of the document
-                assertion: (Default::default(), claimer.clone()),
-            };
-
-            // register property under type of claim
-            if let Some(mut property_ids) = self.claims.get(&property_type_id) {
-                // append to the list if it doesn't contain it already
-                if !property_ids.contains(&property_id) {
-                    property_ids.push(property_id.clone());
-                }
+            if property_id.is_empty() {
+                return Err(Error::EmptyPropertyId);
+            }
 
-                self.claims.insert(property_type_id.clone(), &property_ids);
-            } else {
-                // create new class of properties and add the new one to it
-                let property_ids = vec![property_id.clone()];
+            // an existing property can only change hands through a transfer, never be claimed over
+            if self.properties.contains(&property_id) {
+                return Err(Error::PropertyAlreadyExists(property_id));
+            }
 
-                // insert into contract storage
-                self.claims.insert(property_type_id.clone(), &property_ids);
+            // claims can only be filed under a registered type, or no authority could ever attest them
+            if !self.type_owner.contains(&property_type_id) {
+                return Err(Error::UnknownPropertyType);
             }
 
-            // register (unattested) property claim onchain
-            self.properties.insert(property_id.clone(), &property);
+            // make sure the claim document can be resolved
+            if !Self::is_plausible_cid(&claim_ipfs_addr) {
+                return Err(Error::InvalidCid);
+            }
 
-            // Emit event
-            self.env().emit_event(PropertyClaimRegistered {
+            self.file_claim(
                 claimer,
                 property_type_id,
                 property_id,
-            });
+                claim_ipfs_addr,
+                office,
+            );
 
             Ok(())
         }
 
-        /// Returns a list of property (claims) IDs registered according to a particular property type
-        /// The property IDs are separated by the '#' character
+        /// Submit a claim to a particular property, with the property id derived by the contract.
+        /// The id is `derive_property_id` of the caller, the type and `nonce`, and is returned
         #[ink(message, payable)]
-        pub fn property_claims(&self, property_type_id: PropertyTypeId) -> Vec<u8> {
-            if let Some(property_ids) = self.claims.get(&property_type_id) {
-                property_ids
-                    .into_iter()
-                    .fold(Vec::new(), |mut ids, inner_vec| {
-                        ids.extend(inner_vec);
-                        ids.push(b'#');
-                        ids
-                    })
-            } else {
-                Default::default()
+        pub fn register_claim_auto(
+            &mut self,
+            property_type_id: PropertyTypeId,
+            nonce: u64,
+            claim_ipfs_addr: PropertyClaimAddr,
+            office: Office,
+        ) -> Result<PropertyId> {
+            // get claimer
+            let claimer = Self::env().caller();
+
+            // claims can only be filed under a registered type, or no authority could ever attest them
+            if !self.type_owner.contains(&property_type_id) {
+                return Err(Error::UnknownPropertyType);
+            }
+
+            // make sure the claim document can be resolved
+            if !Self::is_plausible_cid(&claim_ipfs_addr) {
+                return Err(Error::InvalidCid);
             }
+
+            // the same claimer, type and nonce always derive the same id, so it can only be claimed once
+            let property_id = self.derive_property_id(claimer, property_type_id.clone(), nonce);
+            if self.properties.contains(&property_id) {
+                return Err(Error::PropertyAlreadyExists(property_id));
+            }
+
+            self.file_claim(
+                claimer,
+                property_type_id,
+                property_id.clone(),
+                claim_ipfs_addr,
+                office,
+            );
+
+            Ok(property_id)
         }
 
-        /// Return the details of a property
-        /// The claimer is returned as the first element of the tuple
-        /// The default value of the claimer is the caller.
-        /// The vector is the claimers parsable account id + the claim's IPFS address + the property type ID separated by a '$' character
+        /// Derive a canonical property id from a claimer, a property type and a nonce.
+        /// The id is the Blake2x256 hash of the inputs in lowercase hex, so it never contains a separator character
+        #[ink(message)]
+        pub fn derive_property_id(
+            &self,
+            claimer: AccountId,
+            property_type_id: PropertyTypeId,
+            nonce: u64,
+        ) -> PropertyId {
+            let hash = self
+                .env()
+                .hash_bytes::<Blake2x256>(&(claimer, property_type_id, nonce).encode());
+
+            const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+            hash.iter()
+                .flat_map(|byte| {
+                    [
+                        HEX_DIGITS[(byte >> 4) as usize],
+                        HEX_DIGITS[(byte & 0x0f) as usize],
+                    ]
+                })
+                .collect()
+        }
+
+        /// Submit claims to many properties of the same type in one call.
+        /// The batch is all-or-nothing: if any claim is invalid none of them are registered.
+        /// A batch can hold at most `MAX_BATCH_SIZE` claims, all processed by the same `office`
         #[ink(message, payable)]
-        pub fn property_detail(&self, property_id: PropertyId) -> Vec<u8> {
-            let mut return_vec = Vec::new();
+        pub fn register_claims_batch(
+            &mut self,
+            property_type_id: PropertyTypeId,
+            claims: Vec<(PropertyId, PropertyClaimAddr)>,
+            office: Office,
+        ) -> Result<()> {
+            // get claimer
+            let claimer = Self::env().caller();
 
-            if let Some(property) = self.properties.get(&property_id) {
-                // get parsable account ID mapping to the claimers ID
-                if let Some(account_id) = self.account_ids.get(&property.claimer) {
-                    return_vec.extend(account_id.iter());
-                    return_vec.push(b'$');
+            if claims.len() > MAX_BATCH_SIZE {
+                return Err(Error::BatchTooLarge);
+            }
+
+            // claims can only be filed under a registered type, or no authority could ever attest them
+            if !self.type_owner.contains(&property_type_id) {
+                return Err(Error::UnknownPropertyType);
+            }
+
+            // validate every claim before registering any of them
+            if claims.iter().any(|(property_id, _)| property_id.is_empty()) {
+                return Err(Error::EmptyPropertyId);
+            }
+
+            if claims
+                .iter()
+                .any(|(_, claim_ipfs_addr)| !Self::is_plausible_cid(claim_ipfs_addr))
+            {
+                return Err(Error::InvalidCid);
+            }
+
+            // an id can neither be on record already nor be claimed twice in the batch
+            for (index, (property_id, _)) in claims.iter().enumerate() {
+                if self.properties.contains(property_id)
+                    || claims[..index].iter().any(|(id, _)| id == property_id)
+                {
+                    return Err(Error::PropertyAlreadyExists(property_id.clone()));
                 }
+            }
 
-                return_vec.extend(property.property_claim_addr.clone());
-                return_vec.push(b'$');
-                return_vec.extend(property.property_type_id.clone());
+            for (property_id, claim_ipfs_addr) in claims {
+                self.file_claim(
+                    claimer,
+                    property_type_id.clone(),
+                    property_id,
+                    claim_ipfs_addr,
+                    office.clone(),
+                );
             }
 
-            return_vec
+            Ok(())
         }
 
-        /// Transfer a property (or parts of it) from one user to the other
-        /// If a part of the property is transferred, the new properties automatically becomes unattested and have to be signed afresh
+        /// Import a property record migrated from an external (legacy) registry on behalf of its owner.
+        /// The property is recorded as digitized and linked to its id in the old registry.
+        /// Only the contract owner can import records, and each external id can only be imported once
         #[ink(message, payable)]
-        pub fn transfer_property(
+        pub fn import_property(
             &mut self,
+            external_id: ExternalRegistryId,
+            property_type_id: PropertyTypeId,
             property_id: PropertyId,
-            recipient: AccountId,
-            senders_claim_ipfs_addr: PropertyClaimAddr,
-            senders_property_id: PropertyId,
-            recipients_claim_ipfs_addr: PropertyClaimAddr,
-            recipients_property_id: PropertyId,
-            time_of_transfer: PropertyTransferTimestamp,
+            owner: AccountId,
+            claim_ipfs_addr: PropertyClaimAddr,
         ) -> Result<()> {
-            // get caller (which is the account making the transfer)
+            // get caller (which is the contract owner)
             let caller = Self::env().caller();
+            self.ensure_owner()?;
 
-            // check to prevent transfer to self
-            if recipient == caller {
-                return Err(Error::CannotTransferToSelf);
+            if property_id.is_empty() || external_id.is_empty() {
+                return Err(Error::EmptyPropertyId);
             }
 
-            // get the property
-            if let Some(mut property) = self.properties.get(&property_id) {
-                // check if the property is being transferred as a whole
-                if recipients_claim_ipfs_addr.len() != 0 {
-                    // it wasn't
-                    // delete the claims IPFS address because it is invalid now
-                    if let Some(ids) = self.claims.get(&property.property_type_id) {
-                        let filtered_ids = ids
-                            .iter()
-                            .filter(|&id| id != &property_id)
-                            .cloned()
-                            .collect::<Vec<PropertyId>>();
+            if self.external_ids.contains(&external_id) {
+                return Err(Error::DuplicateExternalId);
+            }
 
-                        self.claims
-                            .insert(&property.property_type_id, &filtered_ids);
-                    }
+            // an import can never replace a property already on record
+            if self.properties.contains(&property_id) {
+                return Err(Error::PropertyAlreadyExists(property_id));
+            }
 
-                    // now delete the (old whole) property record
-                    self.properties.remove(&property_id);
+            // claims can only be filed under a registered type, or no authority could ever attest them
+            if !self.type_owner.contains(&property_type_id) {
+                return Err(Error::UnknownPropertyType);
+            }
 
-                    // register new property under type of claim
-                    if let Some(mut property_ids) = self.claims.get(&property.property_type_id) {
-                        // append to the list if it doesn't contain it already
-                        if !property_ids.contains(&senders_property_id) {
-                            property_ids.push(senders_property_id.clone());
-                        }
+            // make sure the claim document can be resolved
+            if !Self::is_plausible_cid(&claim_ipfs_addr) {
+                return Err(Error::InvalidCid);
+            }
 
-                        if !property_ids.contains(&recipients_property_id) {
-                            property_ids.push(recipients_property_id.clone());
-                        }
+            self.file_claim(
+                owner,
+                property_type_id,
+                property_id.clone(),
+                claim_ipfs_addr,
+                Default::default(),
+            );
 
-                        // insert the two new property IDs into storage
-                        self.claims
-                            .insert(property.property_type_id.clone(), &property_ids);
-                    } else {
-                        // create new class of properties and add the new one to it
-                        let property_ids =
-                            vec![senders_property_id.clone(), recipients_property_id.clone()];
+            // the paper record has been digitized by the import itself
+            if let Some(mut property) = self.properties.get(&property_id) {
+                property.digitized = true;
+                property.digitized_by = caller;
+                self.properties.insert(&property_id, &property);
+            }
 
-                        // insert into contract storage
-                        self.claims
-                            .insert(property.property_type_id.clone(), &property_ids);
-                    }
+            self.external_ids.insert(&external_id, &property_id);
+            self.imported_from.insert(&property_id, &external_id);
 
-                    // create a new property document for the sender
-                    let senders_property = Property {
-                        claimer: caller.clone(),
-                        property_claim_addr: senders_claim_ipfs_addr,
-                        property_type_id: property.property_type_id.clone(),
-                        transfer_history: vec![(caller.clone(), time_of_transfer.clone())],
-                        assertion: (Default::default(), caller.clone()),
-                    };
-
-                    // create a new property document for the recipients
-                    let recipients_property = Property {
-                        claimer: recipient.clone(),
-                        property_claim_addr: recipients_claim_ipfs_addr,
-                        property_type_id: property.property_type_id.clone(),
-                        transfer_history: vec![(caller.clone(), time_of_transfer)],
-                        assertion: (Default::default(), recipient.clone()),
-                    };
-
-                    // register the both (unattested) property claims onchain
-                    self.properties
-                        .insert(senders_property_id.clone(), &senders_property);
-                    self.properties
-                        .insert(recipients_property_id.clone(), &recipients_property);
-                } else {
-                    // The property was tranferred as a whole
-                    // Here we need not do much, just change the property claimer
-                    // Then we add the time of transfer and the id of the previous owner
-                    property.claimer = recipient;
-                    property.property_claim_addr = senders_claim_ipfs_addr;
-                    property.transfer_history.push((caller, time_of_transfer));
-
-                    // save to contract storage
-                    self.properties.insert(property_id.clone(), &property);
-                }
+            Ok(())
+        }
 
-                // emit event
-                self.env().emit_event(PropertyTransferred {
-                    sender: caller,
-                    recipient,
-                    property_id,
-                });
+        /// Return the id a property had in the external registry it was imported from.
+        /// An empty vector is returned if the property was not imported
+        #[ink(message)]
+        pub fn external_id_of(&self, property_id: PropertyId) -> ExternalRegistryId {
+            self.imported_from.get(&property_id).unwrap_or_default()
+        }
+
+        /// Drop the ids listed under a property type whose property record no longer exists or is now filed under another type.
+        /// It returns the number of ids removed. Only the contract owner can purge claims
+        #[ink(message, payable)]
+        pub fn purge_orphan_claims(&mut self, property_type_id: PropertyTypeId) -> Result<u32> {
+            self.ensure_owner()?;
+
+            let mut property_ids = self.claims.get(&property_type_id).unwrap_or_default();
+            let claim_count = property_ids.len();
+            property_ids.retain(|property_id| {
+                self.properties
+                    .get(property_id)
+                    .is_some_and(|property| property.property_type_id == property_type_id)
+            });
+            let removed = claim_count.saturating_sub(property_ids.len());
+
+            if removed > 0 {
+                self.claims.insert(&property_type_id, &property_ids);
+                self.total_claims = self.total_claims.saturating_sub(removed as u64);
             }
 
+            Ok(removed as u32)
+        }
+
+        /// Announce an existing claim again so that indexers that missed the original event can catch up.
+        /// Nothing in storage changes. Only the contract owner can reannounce a claim
+        #[ink(message, payable)]
+        pub fn reannounce_claim(&mut self, property_id: PropertyId) -> Result<()> {
+            self.ensure_owner()?;
+
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+
+            // Emit event
+            self.env().emit_event(PropertyClaimRegistered {
+                claimer: property.claimer,
+                property_type_id: property.property_type_id,
+                property_id,
+            });
+
             Ok(())
         }
 
-        /// Sign a property document and cement the owner as the undisputed rightful owner of the property.
-        /// It returns an error if the attested is unauthorized to attest ownership.
-        /// Authorization is gotten by checking for equality between the account that created the property type and the attesting account
+        /// Remove a fraudulent claim from the registry.
+        /// Only the contract owner can do this, and it works whoever the claimer is and whether or not the claim was attested
         #[ink(message, payable)]
-        pub fn sign_document(
+        pub fn force_revoke_claim(&mut self, property_id: PropertyId) -> Result<()> {
+            // get caller (which is the contract owner)
+            let caller = Self::env().caller();
+            self.ensure_owner()?;
+
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+
+            // delete the claim and everything recorded against it
+            self.purge_property(&property, &property_id);
+
+            // emit event
+            self.env().emit_event(ClaimForceRevoked {
+                admin: caller,
+                claimer: property.claimer,
+                property_id,
+            });
+
+            Ok(())
+        }
+
+        /// Replace the IPFS claim document of a property that has not yet been attested.
+        /// Only the claimer can do this. Any signatures already gathered are dropped since they were made on the old document
+        #[ink(message, payable)]
+        pub fn update_claim_address(
             &mut self,
             property_id: PropertyId,
-            property_type_id: PropertyTypeId,
-            assertion_timestamp: AssertionTimestamp,
+            new_claim_ipfs_addr: PropertyClaimAddr,
         ) -> Result<()> {
-            // get caller (which is the account making the attestation)
-            let caller = Self::env().caller();
+            // get claimer
+            let claimer = Self::env().caller();
 
-            // check that only the authorized account can sign.
-            if let Some(property_types) = self.registrations.get(&caller) {
-                if !property_types
-                    .iter()
-                    .any(|ptype| ptype.id == property_type_id)
-                {
-                    // error! unauthorized
-                    return Err(Error::UnauthorizedAccount);
-                }
+            // make sure the claim document can be resolved
+            if !Self::is_plausible_cid(&new_claim_ipfs_addr) {
+                return Err(Error::InvalidCid);
             }
 
-            // now sign document
-            if let Some(mut property) = self.properties.get(&property_id) {
-                property.assertion = (assertion_timestamp, caller.clone());
-
-                // update property
-                self.properties.insert(&property_id, &property);
+            let mut property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if property.claimer != claimer {
+                return Err(Error::NotPropertyOwner);
+            }
 
-                // emit event
-                self.env().emit_event(PropertyDocumentSigned {
-                    attester: caller,
-                    property_id,
-                });
+            // changing an attested document would require it to be signed afresh
+            if !property.assertion.0.is_empty() {
+                return Err(Error::AlreadyAttested);
             }
 
+            property.property_claim_addr = new_claim_ipfs_addr.clone();
+            self.unindex_signers(&property, &property_id);
+            property.signatures.clear();
+            property.last_modified_block = self.env().block_number();
+            self.properties.insert(&property_id, &property);
+
+            // Emit event
+            self.env().emit_event(ClaimAddressUpdated {
+                claimer,
+                property_id,
+                claim_ipfs_addr: new_claim_ipfs_addr,
+            });
+
             Ok(())
         }
 
-        /// Return the verification status of a property.
-        /// This verification status includes: 1. AccountIds showing transfer History 2. AssertionTimestamp
-        /// The accountId's showing transfer history are separated with a '$' character.
-        /// The history is separated from the timestamp by a '@' character
+        /// Move a claim filed under the wrong property type to another type, keeping its id and history.
+        /// Only the claimer can do this, and only while the claim is unattested.
+        /// Any signatures already gathered and any approved subdivision plan are dropped since they were made by authorities of the old type
         #[ink(message, payable)]
-        pub fn attestation_status(&self, property_id: PropertyId) -> Vec<u8> {
-            // the vector we are returning, containing all the accountIds that have had possession of the property
-            let mut transfer_history = Vec::new();
-        
-            if let Some(property) = self.properties.get(&property_id) {
-                // we need to return AccountIdVec, hence we need to make the conversion
-                for (account_id, _) in &property.transfer_history {
-                    transfer_history.push(self.convert_accountid_to_vec(account_id));
-                }
-        
-                // Flatten and concatenate the vectors in transfer_history
-                let mut flattened_history = Vec::new();
-                for inner_vec in &transfer_history {
-                    flattened_history.extend(inner_vec.iter().copied());
-                    flattened_history.push(b'$');
-                }
-        
-                // append the assertion timestamp to it
-                flattened_history.push(b'@');
-                flattened_history.extend(property.assertion.0.iter());
-                flattened_history
-            } else {
-                // 0 is the flag to indicate that the property has not been attested
-                Default::default()
+        pub fn reclassify_property(
+            &mut self,
+            property_id: PropertyId,
+            new_property_type_id: PropertyTypeId,
+        ) -> Result<()> {
+            // get claimer
+            let claimer = Self::env().caller();
+
+            let mut property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if property.claimer != claimer {
+                return Err(Error::NotPropertyOwner);
             }
-        }
 
-        /// Helper function to convert an AccountId into an AccountIdvec.
-        /// It uses the account_ids mapping property of our contract storage
-        pub fn convert_accountid_to_vec(&self, account_id: &AccountId) -> AccountIdVec {
-            if let Some(account_id_vec) = self.account_ids.get(account_id) {
-                account_id_vec
-            } else {
-                Default::default()
+            if !property.assertion.0.is_empty() {
+                return Err(Error::AlreadyAttested);
+            }
+
+            if !self.type_owner.contains(&new_property_type_id) {
+                return Err(Error::UnknownPropertyType);
             }
+
+            let old_property_type_id = property.property_type_id.clone();
+            self.unindex_claim(&old_property_type_id, &property_id);
+            self.index_claim(&new_property_type_id, &property_id);
+
+            property.property_type_id = new_property_type_id.clone();
+            self.unindex_signers(&property, &property_id);
+            property.signatures.clear();
+            property.last_modified_block = self.env().block_number();
+            self.properties.insert(&property_id, &property);
+            self.subdivisions.remove(&property_id);
+
+            // Emit event
+            self.env().emit_event(PropertyReclassified {
+                property_id,
+                old_property_type_id,
+                new_property_type_id,
+            });
+
+            Ok(())
+        }
+
+        /// Returns a list of property (claims) IDs registered according to a particular property type
+        /// The property IDs are separated by the '#' character
+        #[ink(message)]
+        pub fn property_claims(&self, property_type_id: PropertyTypeId) -> Vec<u8> {
+            if let Some(property_ids) = self.claims.get(&property_type_id) {
+                property_ids
+                    .into_iter()
+                    .fold(Vec::new(), |mut ids, inner_vec| {
+                        ids.extend(inner_vec);
+                        ids.push(b'#');
+                        ids
+                    })
+            } else {
+                Default::default()
+            }
+        }
+
+        /// Returns the ids of the property types that have no claims registered under them.
+        /// The ids are separated by the '#' character
+        #[ink(message)]
+        pub fn empty_types(&self) -> Vec<u8> {
+            self.all_type_ids
+                .get_or_default()
+                .iter()
+                .filter(|property_type_id| {
+                    self.claims
+                        .get(*property_type_id)
+                        .unwrap_or_default()
+                        .is_empty()
+                })
+                .fold(Vec::new(), |mut ids, property_type_id| {
+                    ids.extend(property_type_id);
+                    ids.push(b'#');
+                    ids
+                })
+        }
+
+        /// Returns the property (claims) IDs registered under a property type that are still waiting to be attested
+        #[ink(message)]
+        pub fn claims_awaiting_attestation(
+            &self,
+            property_type_id: PropertyTypeId,
+        ) -> Vec<PropertyId> {
+            self.claims_awaiting_attestation_paged(property_type_id, 0, u32::MAX)
+        }
+
+        /// Paged variant of `claims_awaiting_attestation`.
+        /// It examines at most `limit` claims of the type, starting from the claim at index `start`
+        #[ink(message)]
+        pub fn claims_awaiting_attestation_paged(
+            &self,
+            property_type_id: PropertyTypeId,
+            start: u32,
+            limit: u32,
+        ) -> Vec<PropertyId> {
+            self.claims
+                .get(&property_type_id)
+                .unwrap_or_default()
+                .into_iter()
+                .skip(start as usize)
+                .take(limit as usize)
+                .filter(|property_id| {
+                    self.properties
+                        .get(property_id)
+                        .is_some_and(|property| property.assertion.0.is_empty())
+                })
+                .collect()
+        }
+
+        /// Returns the number of attested and unattested properties registered under a property type, in that order
+        #[ink(message)]
+        pub fn type_stats(&self, property_type_id: PropertyTypeId) -> (u32, u32) {
+            self.type_stats_paged(property_type_id, 0, u32::MAX)
+        }
+
+        /// Paged variant of `type_stats`.
+        /// It examines at most `limit` claims of the type, starting from the claim at index `start`
+        #[ink(message)]
+        pub fn type_stats_paged(
+            &self,
+            property_type_id: PropertyTypeId,
+            start: u32,
+            limit: u32,
+        ) -> (u32, u32) {
+            let mut attested: u32 = 0;
+            let mut unattested: u32 = 0;
+            for property_id in self
+                .claims
+                .get(&property_type_id)
+                .unwrap_or_default()
+                .iter()
+                .skip(start as usize)
+                .take(limit as usize)
+            {
+                if let Some(property) = self.properties.get(property_id) {
+                    if property.assertion.0.is_empty() {
+                        unattested = unattested.saturating_add(1);
+                    } else {
+                        attested = attested.saturating_add(1);
+                    }
+                }
+            }
+
+            (attested, unattested)
+        }
+
+        /// Returns the property (claims) IDs registered under a property type that are still unattested
+        /// and were registered more than `older_than` blocks ago.
+        /// The property IDs are separated by the '#' character
+        #[ink(message)]
+        pub fn stale_claims(&self, property_type_id: PropertyTypeId, older_than: u32) -> Vec<u8> {
+            let cutoff = self.env().block_number().saturating_sub(older_than);
+
+            self.claims
+                .get(&property_type_id)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|property_id| {
+                    self.properties.get(property_id).is_some_and(|property| {
+                        property.assertion.0.is_empty() && property.claimed_at < cutoff
+                    })
+                })
+                .fold(Vec::new(), |mut ids, property_id| {
+                    ids.extend(property_id);
+                    ids.push(b'#');
+                    ids
+                })
+        }
+
+        /// Returns the Blake2x256 merkle root of the property (claims) IDs registered under a property type.
+        /// The IDs are sorted before hashing, and an odd node at any level is carried up unchanged.
+        /// Leaves are hashed with a 0x00 prefix and inner nodes with a 0x01 prefix, so that one can never pass for the other.
+        /// A type with no claims returns the zero hash
+        #[ink(message)]
+        pub fn type_claims_root(&self, property_type_id: PropertyTypeId) -> [u8; 32] {
+            let mut property_ids = self.claims.get(&property_type_id).unwrap_or_default();
+            if property_ids.is_empty() {
+                return [0u8; 32];
+            }
+            property_ids.sort();
+
+            // hash the leaves
+            let mut level: Vec<[u8; 32]> = property_ids
+                .iter()
+                .map(|id| {
+                    let mut leaf = vec![0x00];
+                    leaf.extend_from_slice(id);
+                    self.env().hash_bytes::<Blake2x256>(&leaf)
+                })
+                .collect();
+
+            // hash pairs of nodes until only the root remains
+            while level.len() > 1 {
+                level = level
+                    .chunks(2)
+                    .map(|pair| match pair {
+                        [left, right] => {
+                            let mut node = vec![0x01];
+                            node.extend_from_slice(left);
+                            node.extend_from_slice(right);
+                            self.env().hash_bytes::<Blake2x256>(&node)
+                        }
+                        [single] => *single,
+                        _ => unreachable!(),
+                    })
+                    .collect();
+            }
+
+            level[0]
+        }
+
+        /// Return whether a property exists
+        #[ink(message)]
+        pub fn property_exists(&self, property_id: PropertyId) -> bool {
+            self.properties.contains(&property_id)
+        }
+
+        /// Return whether each of the given properties exists, in the order they were given
+        #[ink(message)]
+        pub fn properties_exist(&self, ids: Vec<PropertyId>) -> Vec<bool> {
+            ids.iter().map(|id| self.properties.contains(id)).collect()
+        }
+
+        /// Return the account that currently owns a property, or `None` if the property does not exist
+        #[ink(message)]
+        pub fn claimer_of(&self, property_id: PropertyId) -> Option<AccountId> {
+            self.properties
+                .get(&property_id)
+                .map(|property| property.claimer)
+        }
+
+        /// Return the share of the original property a property represents, in basis points.
+        /// A property that was never split holds the full 10000. Zero is returned if the property does not exist
+        #[ink(message)]
+        pub fn share_of(&self, property_id: PropertyId) -> u16 {
+            self.properties
+                .get(&property_id)
+                .map(|property| property.share_bps)
+                .unwrap_or_default()
+        }
+
+        /// Return the IPFS address (CID) of the claim document of a property, or `None` if the property does not exist
+        #[ink(message)]
+        pub fn claim_address(&self, property_id: PropertyId) -> Option<PropertyClaimAddr> {
+            self.properties
+                .get(&property_id)
+                .map(|property| property.property_claim_addr)
+        }
+
+        /// Return the property types an account holds properties under, without duplicates
+        #[ink(message)]
+        pub fn claimed_types_of(&self, account_id: AccountId) -> Vec<PropertyTypeId> {
+            let mut property_type_ids: Vec<PropertyTypeId> = Vec::new();
+            for property_id in self.owned_properties.get(account_id).unwrap_or_default() {
+                if let Some(property) = self.properties.get(&property_id) {
+                    if !property_type_ids.contains(&property.property_type_id) {
+                        property_type_ids.push(property.property_type_id);
+                    }
+                }
+            }
+
+            property_type_ids
+        }
+
+        /// Return the number of properties an account owns
+        #[ink(message)]
+        pub fn properties_count_by_owner(&self, owner: AccountId) -> u32 {
+            self.owned_properties.get(owner).unwrap_or_default().len() as u32
+        }
+
+        /// Return the notary or registrar office that processed the claim to a property.
+        /// An empty vector is returned if the property does not exist
+        #[ink(message)]
+        pub fn office_of(&self, property_id: PropertyId) -> Office {
+            self.properties
+                .get(&property_id)
+                .map(|property| property.office)
+                .unwrap_or_default()
+        }
+
+        /// Set an attribute of a property, overwriting the value if the key is already set.
+        /// Only the claimer can set attributes, and a property can carry at most `MAX_ATTRIBUTES` of them
+        #[ink(message, payable)]
+        pub fn set_attribute(
+            &mut self,
+            property_id: PropertyId,
+            key: AttributeKey,
+            value: AttributeValue,
+        ) -> Result<()> {
+            // get claimer
+            let claimer = Self::env().caller();
+
+            let mut property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if property.claimer != claimer {
+                return Err(Error::NotPropertyOwner);
+            }
+
+            match property
+                .attributes
+                .iter_mut()
+                .find(|(existing_key, _)| existing_key == &key)
+            {
+                Some((_, existing_value)) => *existing_value = value.clone(),
+                None => {
+                    if property.attributes.len() >= MAX_ATTRIBUTES {
+                        return Err(Error::TooManyAttributes);
+                    }
+                    property.attributes.push((key.clone(), value.clone()));
+                }
+            }
+            property.last_modified_block = self.env().block_number();
+            self.properties.insert(&property_id, &property);
+
+            // Emit event
+            self.env().emit_event(AttributeSet {
+                property_id,
+                key,
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Return the attributes of a property
+        #[ink(message)]
+        pub fn get_attributes(
+            &self,
+            property_id: PropertyId,
+        ) -> Vec<(AttributeKey, AttributeValue)> {
+            self.properties
+                .get(&property_id)
+                .map(|property| property.attributes)
+                .unwrap_or_default()
+        }
+
+        /// Return the details of a property
+        /// The claimer is returned as the first element of the tuple
+        /// The default value of the claimer is the caller.
+        /// The vector is the claimers parsable account id + the claim's IPFS address + the property type ID separated by a '$' character
+        #[ink(message)]
+        pub fn property_detail(&self, property_id: PropertyId) -> Vec<u8> {
+            let mut return_vec = Vec::new();
+
+            if let Some(property) = self.properties.get(&property_id) {
+                // get parsable account ID mapping to the claimers ID
+                if let Some(account_id) = self.account_ids.get(&property.claimer) {
+                    return_vec.extend(account_id.iter());
+                    return_vec.push(b'$');
+                }
+
+                return_vec.extend(property.property_claim_addr.clone());
+                return_vec.push(b'$');
+                return_vec.extend(property.property_type_id.clone());
+            }
+
+            return_vec
+        }
+
+        /// Return the flags and counters describing the state of a property in one call.
+        /// `None` is returned if the property does not exist
+        #[ink(message)]
+        pub fn property_meta(&self, property_id: PropertyId) -> Option<PropertyMeta> {
+            let property = self.properties.get(&property_id)?;
+
+            Some(PropertyMeta {
+                disputed: property.disputed,
+                encumbered: self.is_encumbered(&property_id),
+                attested: !property.assertion.0.is_empty(),
+                transfer_count: self
+                    .transfer_histories
+                    .get(&property_id)
+                    .unwrap_or_default()
+                    .len() as u32,
+                charge_count: self
+                    .government_charges
+                    .get(&property_id)
+                    .unwrap_or_default()
+                    .len() as u32,
+                claimed_at: property.claimed_at,
+                last_modified_block: property.last_modified_block,
+            })
+        }
+
+        /// Transfer a property (or parts of it) from one user to the other
+        /// If a part of the property is transferred, the new properties automatically becomes unattested and have to be signed afresh.
+        /// This is kept for backward compatibility: an empty `recipients_claim_ipfs_addr` makes it a `transfer_whole`, otherwise a `split_and_transfer`
+        /// of the property's share in two even halves.
+        /// `transfer_doc_ipfs_addr` is the legal instrument behind the transfer, recorded in the chain of custody. It can be empty
+        // both parts of a split and the transfer document are passed in full, hence the long parameter list
+        #[allow(clippy::too_many_arguments)]
+        #[ink(message, payable)]
+        pub fn transfer_property(
+            &mut self,
+            property_id: PropertyId,
+            recipient: AccountId,
+            senders_claim_ipfs_addr: PropertyClaimAddr,
+            senders_property_id: PropertyId,
+            recipients_claim_ipfs_addr: PropertyClaimAddr,
+            recipients_property_id: PropertyId,
+            time_of_transfer: PropertyTransferTimestamp,
+            transfer_doc_ipfs_addr: TransferDocAddr,
+        ) -> Result<()> {
+            // check if the property is being transferred as a whole
+            if recipients_claim_ipfs_addr.is_empty() {
+                self.transfer_whole(
+                    property_id,
+                    recipient,
+                    senders_claim_ipfs_addr,
+                    time_of_transfer,
+                    // the legacy path carries no sale contract
+                    [0u8; 32],
+                    transfer_doc_ipfs_addr,
+                )
+            } else {
+                // the legacy path splits the share of the property evenly
+                let share_bps = self
+                    .properties
+                    .get(&property_id)
+                    .map(|property| property.share_bps)
+                    .unwrap_or_default();
+                let recipients_share_bps = share_bps / 2;
+
+                self.split_and_transfer(
+                    property_id,
+                    recipient,
+                    senders_claim_ipfs_addr,
+                    senders_property_id,
+                    recipients_claim_ipfs_addr,
+                    recipients_property_id,
+                    time_of_transfer,
+                    share_bps - recipients_share_bps,
+                    recipients_share_bps,
+                    transfer_doc_ipfs_addr,
+                )
+            }
+        }
+
+        /// Transfer a whole property from one user to the other.
+        /// The property keeps its id and attestation, and `new_claim_ipfs_addr` becomes its claim document.
+        /// `contract_hash` is the hash of the sale contract and `transfer_doc_ipfs_addr` the legal instrument behind the transfer.
+        /// Both are recorded with the transfer in the chain of custody, and the document address can be empty
+        #[ink(message, payable)]
+        pub fn transfer_whole(
+            &mut self,
+            property_id: PropertyId,
+            recipient: AccountId,
+            new_claim_ipfs_addr: PropertyClaimAddr,
+            time_of_transfer: PropertyTransferTimestamp,
+            contract_hash: SaleContractHash,
+            transfer_doc_ipfs_addr: TransferDocAddr,
+        ) -> Result<()> {
+            // get caller (which is the account making the transfer)
+            let caller = Self::env().caller();
+
+            if property_id.is_empty() {
+                return Err(Error::EmptyPropertyId);
+            }
+
+            // make sure the transfer document, if any, can be resolved
+            if !transfer_doc_ipfs_addr.is_empty()
+                && !Self::is_plausible_cid(&transfer_doc_ipfs_addr)
+            {
+                return Err(Error::InvalidCid);
+            }
+
+            // check to prevent transfer to self
+            if recipient == caller {
+                return Err(Error::CannotTransferToSelf);
+            }
+
+            // the recipient must have an account, or its parsable id would be missing from the chain of custody
+            if !self.accounts.contains(recipient) {
+                return Err(Error::RecipientNotRegistered);
+            }
+
+            // get the property
+            let mut property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+
+            // only the owner can give the property away
+            if property.claimer != caller {
+                return Err(Error::NotPropertyOwner);
+            }
+
+            if property.disputed {
+                return Err(Error::PropertyDisputed);
+            }
+
+            if self.is_encumbered(&property_id) {
+                return Err(Error::PropertyEncumbered);
+            }
+
+            self.pay_transfer_fee(&property.property_type_id)?;
+
+            // keep what is needed to reverse the transfer during the type's grace period
+            if self
+                .transfer_grace_blocks
+                .get(&property.property_type_id)
+                .unwrap_or_default()
+                > 0
+            {
+                self.reversible_transfers.insert(
+                    &property_id,
+                    &(
+                        caller,
+                        self.env().block_number(),
+                        property.property_claim_addr.clone(),
+                    ),
+                );
+            } else {
+                self.reversible_transfers.remove(&property_id);
+            }
+
+            // Here we need not do much, just change the property claimer
+            // Then we add the time of transfer and the id of the previous owner
+            property.claimer = recipient;
+            property.property_claim_addr = new_claim_ipfs_addr;
+
+            let mut transfer_history = self
+                .transfer_histories
+                .get(&property_id)
+                .unwrap_or_default();
+            transfer_history.push((
+                caller,
+                time_of_transfer,
+                contract_hash,
+                transfer_doc_ipfs_addr,
+            ));
+
+            // save to contract storage
+            property.last_modified_block = self.env().block_number();
+            self.properties.insert(&property_id, &property);
+            self.transfer_histories
+                .insert(&property_id, &transfer_history);
+
+            self.unindex_owner(caller, &property_id);
+            self.index_owner(recipient, &property_id);
+
+            self.record_transfer(caller, recipient, property_id, TransferKind::Whole);
+
+            Ok(())
+        }
+
+        /// Reverse the latest whole transfer of a property, restoring the caller as its owner.
+        /// Only the previous owner can reverse a transfer, and only within the grace period of the property's type.
+        /// Once the grace period lapses the transfer is final
+        #[ink(message, payable)]
+        pub fn reverse_transfer(&mut self, property_id: PropertyId) -> Result<()> {
+            // get caller (which is the previous owner)
+            let caller = Self::env().caller();
+
+            let (previous_owner, transfer_block, previous_claim_addr) = self
+                .reversible_transfers
+                .get(&property_id)
+                .ok_or(Error::NoReversibleTransfer)?;
+            if previous_owner != caller {
+                return Err(Error::UnauthorizedAccount(caller));
+            }
+
+            let mut property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+
+            // a disputed or encumbered property stays with its current owner
+            if property.disputed {
+                return Err(Error::PropertyDisputed);
+            }
+
+            if self.is_encumbered(&property_id) {
+                return Err(Error::PropertyEncumbered);
+            }
+
+            let grace_blocks = self
+                .transfer_grace_blocks
+                .get(&property.property_type_id)
+                .unwrap_or_default();
+            if self.env().block_number() > transfer_block.saturating_add(grace_blocks) {
+                return Err(Error::GracePeriodElapsed);
+            }
+
+            // restore the previous owner and drop the reversed entry from the chain of custody
+            let reverted_owner = property.claimer;
+            property.claimer = previous_owner;
+            property.property_claim_addr = previous_claim_addr;
+            property.last_modified_block = self.env().block_number();
+            self.properties.insert(&property_id, &property);
+            self.unindex_owner(reverted_owner, &property_id);
+            self.index_owner(previous_owner, &property_id);
+
+            let mut transfer_history = self
+                .transfer_histories
+                .get(&property_id)
+                .unwrap_or_default();
+            transfer_history.pop();
+            self.transfer_histories
+                .insert(&property_id, &transfer_history);
+
+            self.reversible_transfers.remove(&property_id);
+
+            // emit event
+            self.env().emit_event(TransferReversed {
+                previous_owner,
+                reverted_owner,
+                property_id,
+            });
+
+            Ok(())
+        }
+
+        /// Set the number of blocks after a whole transfer during which the previous owner can reverse it.
+        /// Only the original registrar of the type can set it. Zero disables reversal
+        #[ink(message, payable)]
+        pub fn set_transfer_grace_blocks(
+            &mut self,
+            property_type_id: PropertyTypeId,
+            grace_blocks: BlockNumber,
+        ) -> Result<()> {
+            // get caller (which is the registrar)
+            let caller = Self::env().caller();
+
+            if self.type_owner.get(&property_type_id) != Some(caller) {
+                return Err(Error::UnauthorizedAccount(caller));
+            }
+
+            self.transfer_grace_blocks
+                .insert(&property_type_id, &grace_blocks);
+
+            Ok(())
+        }
+
+        /// Return the number of blocks after a whole transfer during which the previous owner can reverse it
+        #[ink(message)]
+        pub fn transfer_grace_blocks(&self, property_type_id: PropertyTypeId) -> BlockNumber {
+            self.transfer_grace_blocks
+                .get(&property_type_id)
+                .unwrap_or_default()
+        }
+
+        /// Split a property in two, keeping one part and transferring the other to the recipient.
+        /// The old property is removed and the two new properties are unattested and have to be signed afresh.
+        /// An authority must have approved a subdivision plan for the property beforehand.
+        /// The shares of the two parts, in basis points, must add up to the share of the property being split.
+        /// `transfer_doc_ipfs_addr` is the legal instrument behind the transfer, recorded in the chain of custody. It can be empty
+        // both parts of the split are described in full, hence the long parameter list
+        #[allow(clippy::too_many_arguments)]
+        #[ink(message, payable)]
+        pub fn split_and_transfer(
+            &mut self,
+            property_id: PropertyId,
+            recipient: AccountId,
+            senders_claim_ipfs_addr: PropertyClaimAddr,
+            senders_property_id: PropertyId,
+            recipients_claim_ipfs_addr: PropertyClaimAddr,
+            recipients_property_id: PropertyId,
+            time_of_transfer: PropertyTransferTimestamp,
+            senders_share_bps: u16,
+            recipients_share_bps: u16,
+            transfer_doc_ipfs_addr: TransferDocAddr,
+        ) -> Result<()> {
+            // get caller (which is the account making the transfer)
+            let caller = Self::env().caller();
+
+            // the old and both new property ids must be usable keys
+            if property_id.is_empty()
+                || senders_property_id.is_empty()
+                || recipients_property_id.is_empty()
+            {
+                return Err(Error::EmptyPropertyId);
+            }
+
+            // the two parts need distinct ids, and only the id of the property being split can be reused
+            if senders_property_id == recipients_property_id {
+                return Err(Error::PropertyAlreadyExists(recipients_property_id));
+            }
+            for new_property_id in [&senders_property_id, &recipients_property_id] {
+                if *new_property_id != property_id && self.properties.contains(new_property_id) {
+                    return Err(Error::PropertyAlreadyExists(new_property_id.clone()));
+                }
+            }
+
+            // make sure the transfer document, if any, can be resolved
+            if !transfer_doc_ipfs_addr.is_empty()
+                && !Self::is_plausible_cid(&transfer_doc_ipfs_addr)
+            {
+                return Err(Error::InvalidCid);
+            }
+
+            // check to prevent transfer to self
+            if recipient == caller {
+                return Err(Error::CannotTransferToSelf);
+            }
+
+            // the recipient must have an account, or its parsable id would be missing from the chain of custody
+            if !self.accounts.contains(recipient) {
+                return Err(Error::RecipientNotRegistered);
+            }
+
+            // get the property
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+
+            // only the owner can give (part of) the property away
+            if property.claimer != caller {
+                return Err(Error::NotPropertyOwner);
+            }
+
+            if property.disputed {
+                return Err(Error::PropertyDisputed);
+            }
+
+            if self.is_encumbered(&property_id) {
+                return Err(Error::PropertyEncumbered);
+            }
+
+            if !self.subdivisions.contains(&property_id) {
+                return Err(Error::SubdivisionNotApproved);
+            }
+
+            // the two parts must account for exactly the share of the property being split
+            if senders_share_bps as u32 + recipients_share_bps as u32 != property.share_bps as u32 {
+                return Err(Error::InvalidShareSplit);
+            }
+
+            self.pay_transfer_fee(&property.property_type_id)?;
+
+            let property_type_id = property.property_type_id.clone();
+
+            // both new properties inherit the chain of custody of the old one
+            let mut transfer_history = self
+                .transfer_histories
+                .get(&property_id)
+                .unwrap_or_default();
+            // a split has no sale contract attached
+            transfer_history.push((caller, time_of_transfer, [0u8; 32], transfer_doc_ipfs_addr));
+
+            // the old whole property is invalid now, delete its claim and everything recorded against it
+            self.purge_property(&property, &property_id);
+
+            // register the new properties under the type of claim
+            self.index_claim(&property_type_id, &senders_property_id);
+            self.index_claim(&property_type_id, &recipients_property_id);
+
+            // create a new property document for the sender
+            let senders_property = Property {
+                claimer: caller,
+                property_claim_addr: senders_claim_ipfs_addr,
+                property_type_id: property_type_id.clone(),
+                assertion: (Default::default(), caller),
+                signatures: Vec::new(),
+                disputed: false,
+                office: property.office.clone(),
+                // the attributes described the whole property, so the parts start without any
+                attributes: Vec::new(),
+                digitized: false,
+                digitized_by: caller,
+                claimed_at: self.env().block_number(),
+                last_modified_block: self.env().block_number(),
+                share_bps: senders_share_bps,
+            };
+
+            // create a new property document for the recipients
+            let recipients_property = Property {
+                claimer: recipient,
+                property_claim_addr: recipients_claim_ipfs_addr,
+                property_type_id,
+                assertion: (Default::default(), recipient),
+                signatures: Vec::new(),
+                disputed: false,
+                office: property.office,
+                attributes: Vec::new(),
+                digitized: false,
+                digitized_by: recipient,
+                claimed_at: self.env().block_number(),
+                last_modified_block: self.env().block_number(),
+                share_bps: recipients_share_bps,
+            };
+
+            // register the both (unattested) property claims onchain
+            self.total_properties = self.total_properties.saturating_add(2);
+            self.index_owner(caller, &senders_property_id);
+            self.index_owner(recipient, &recipients_property_id);
+            self.properties
+                .insert(&senders_property_id, &senders_property);
+            self.properties
+                .insert(&recipients_property_id, &recipients_property);
+            self.transfer_histories
+                .insert(&senders_property_id, &transfer_history);
+            self.transfer_histories
+                .insert(&recipients_property_id, &transfer_history);
+
+            self.record_transfer(
+                caller,
+                recipient,
+                property_id,
+                TransferKind::Split {
+                    senders_property_id,
+                    recipients_property_id,
+                },
+            );
+
+            Ok(())
+        }
+
+        /// Merge adjacent properties of the same type into a single property, the inverse of a split.
+        /// Only the owner of all the properties can merge them. The old properties are removed, and the merged
+        /// property is unattested, has to be signed afresh and inherits the combined chain of custody of the old ones
+        #[ink(message, payable)]
+        pub fn merge_properties(
+            &mut self,
+            property_ids: Vec<PropertyId>,
+            merged_property_id: PropertyId,
+            merged_claim_ipfs_addr: PropertyClaimAddr,
+            time_of_merge: TimeString,
+        ) -> Result<()> {
+            // get caller (which is the owner of the properties)
+            let caller = Self::env().caller();
+
+            if property_ids.len() > MAX_BATCH_SIZE {
+                return Err(Error::BatchTooLarge);
+            }
+
+            // a property listed twice is only merged once
+            let mut merged_from: Vec<PropertyId> = Vec::new();
+            for property_id in property_ids {
+                if !merged_from.contains(&property_id) {
+                    merged_from.push(property_id);
+                }
+            }
+
+            if merged_property_id.is_empty() || merged_from.is_empty() {
+                return Err(Error::EmptyPropertyId);
+            }
+
+            // only the id of one of the merged properties can be reused
+            if !merged_from.contains(&merged_property_id)
+                && self.properties.contains(&merged_property_id)
+            {
+                return Err(Error::PropertyAlreadyExists(merged_property_id));
+            }
+
+            // make sure the claim document can be resolved
+            if !Self::is_plausible_cid(&merged_claim_ipfs_addr) {
+                return Err(Error::InvalidCid);
+            }
+
+            // validate every property before removing any of them
+            let mut properties = Vec::new();
+            for property_id in &merged_from {
+                let property = self
+                    .properties
+                    .get(property_id)
+                    .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+                if property.claimer != caller {
+                    return Err(Error::NotPropertyOwner);
+                }
+
+                if property.disputed {
+                    return Err(Error::PropertyDisputed);
+                }
+
+                if self.is_encumbered(property_id) {
+                    return Err(Error::PropertyEncumbered);
+                }
+
+                properties.push(property);
+            }
+
+            let property_type_id = properties[0].property_type_id.clone();
+            let office = properties[0].office.clone();
+            let share_bps = properties
+                .iter()
+                .map(|property| property.share_bps as u32)
+                .sum::<u32>()
+                .min(FULL_SHARE_BPS as u32) as u16;
+            if properties
+                .iter()
+                .any(|property| property.property_type_id != property_type_id)
+            {
+                return Err(Error::MixedPropertyTypes);
+            }
+
+            // the merged property inherits the chain of custody of all the old ones, without repeated entries
+            let mut transfer_history: Vec<TransferRecord> = Vec::new();
+            for property_id in &merged_from {
+                for record in self.transfer_histories.get(property_id).unwrap_or_default() {
+                    if !transfer_history.contains(&record) {
+                        transfer_history.push(record);
+                    }
+                }
+            }
+            transfer_history.push((caller, time_of_merge, [0u8; 32], Vec::new()));
+
+            // the old properties are invalid now, delete their claims and everything recorded against them
+            for (property_id, property) in merged_from.iter().zip(&properties) {
+                self.purge_property(property, property_id);
+            }
+
+            // register the merged (unattested) property claim onchain
+            self.file_claim(
+                caller,
+                property_type_id,
+                merged_property_id.clone(),
+                merged_claim_ipfs_addr,
+                office,
+            );
+            self.transfer_histories
+                .insert(&merged_property_id, &transfer_history);
+
+            // the merged property represents the combined share of the old ones
+            if let Some(mut merged_property) = self.properties.get(&merged_property_id) {
+                merged_property.share_bps = share_bps;
+                self.properties
+                    .insert(&merged_property_id, &merged_property);
+            }
+
+            // emit event
+            self.env().emit_event(PropertiesMerged {
+                owner: caller,
+                merged_property_id,
+                merged_from,
+            });
+
+            Ok(())
+        }
+
+        /// Transfer a whole property to a recipient, creating an account for the recipient first if they don't have one.
+        /// Only the owner of the property can make the transfer
+        // it takes the recipient's account details along with the transfer's, hence the long parameter list
+        #[allow(clippy::too_many_arguments)]
+        #[ink(message, payable)]
+        pub fn transfer_to_new(
+            &mut self,
+            property_id: PropertyId,
+            recipient: AccountId,
+            recipient_name: Vec<u8>,
+            recipient_id_vec: AccountIdVec,
+            claim_ipfs_addr: PropertyClaimAddr,
+            time_of_transfer: PropertyTransferTimestamp,
+            contract_hash: SaleContractHash,
+            transfer_doc_ipfs_addr: TransferDocAddr,
+        ) -> Result<()> {
+            // get caller (which is the account making the transfer)
+            let caller = Self::env().caller();
+
+            // check to prevent transfer to self
+            if recipient == caller {
+                return Err(Error::CannotTransferToSelf);
+            }
+
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if property.claimer != caller {
+                return Err(Error::NotPropertyOwner);
+            }
+
+            // onboard the recipient
+            if !self.accounts.contains(recipient) {
+                if !Self::is_valid_name(&recipient_name) {
+                    return Err(Error::InvalidNameLength);
+                }
+
+                let new_account = AccountInfo {
+                    name: recipient_name.clone(),
+                    timestamp: time_of_transfer.clone(),
+                };
+
+                self.accounts.insert(recipient, &new_account);
+                self.account_ids.insert(recipient, &recipient_id_vec);
+                self.total_accounts = self.total_accounts.saturating_add(1);
+
+                self.env().emit_event(AccountCreated {
+                    account_id: recipient,
+                    name: recipient_name,
+                });
+            }
+
+            self.transfer_whole(
+                property_id,
+                recipient,
+                claim_ipfs_addr,
+                time_of_transfer,
+                contract_hash,
+                transfer_doc_ipfs_addr,
+            )
+        }
+
+        /// Authorize a deputy to sign documents of a property type on the registrar's behalf.
+        /// Only the original registrar of the type can add signers
+        #[ink(message, payable)]
+        pub fn add_signer(
+            &mut self,
+            property_type_id: PropertyTypeId,
+            deputy: AccountId,
+        ) -> Result<()> {
+            // get caller (which is the registrar delegating its authority)
+            let caller = Self::env().caller();
+
+            if self.type_owner.get(&property_type_id) != Some(caller) {
+                return Err(Error::UnauthorizedAccount(caller));
+            }
+
+            let mut signers = self
+                .delegated_signers
+                .get(&property_type_id)
+                .unwrap_or_default();
+            if !signers.contains(&deputy) {
+                signers.push(deputy);
+                self.delegated_signers.insert(&property_type_id, &signers);
+            }
+
+            // emit event
+            self.env().emit_event(SignerAdded {
+                registrar: caller,
+                deputy,
+                property_type_id,
+            });
+
+            Ok(())
+        }
+
+        /// Revoke a deputy's authority to sign documents of a property type.
+        /// Only the original registrar of the type can remove signers
+        #[ink(message, payable)]
+        pub fn remove_signer(
+            &mut self,
+            property_type_id: PropertyTypeId,
+            deputy: AccountId,
+        ) -> Result<()> {
+            // get caller (which is the registrar revoking the authority)
+            let caller = Self::env().caller();
+
+            if self.type_owner.get(&property_type_id) != Some(caller) {
+                return Err(Error::UnauthorizedAccount(caller));
+            }
+
+            let mut signers = self
+                .delegated_signers
+                .get(&property_type_id)
+                .unwrap_or_default();
+            signers.retain(|signer| *signer != deputy);
+            self.delegated_signers.insert(&property_type_id, &signers);
+
+            // emit event
+            self.env().emit_event(SignerRemoved {
+                registrar: caller,
+                deputy,
+                property_type_id,
+            });
+
+            Ok(())
+        }
+
+        /// Approve the plan to subdivide a property, allowing it to be split.
+        /// Only an authority that registered the property's type can approve it
+        #[ink(message, payable)]
+        pub fn approve_subdivision(
+            &mut self,
+            property_id: PropertyId,
+            plan_ipfs_addr: SubdivisionPlanAddr,
+        ) -> Result<()> {
+            // get caller (which is the authority approving the plan)
+            let caller = Self::env().caller();
+
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if !self.is_registrar(caller, &property.property_type_id) {
+                return Err(Error::UnauthorizedAccount(caller));
+            }
+
+            self.subdivisions.insert(&property_id, &plan_ipfs_addr);
+
+            // emit event
+            self.env().emit_event(SubdivisionApproved {
+                authority: caller,
+                property_id,
+                plan_ipfs_addr,
+            });
+
+            Ok(())
+        }
+
+        /// Review a claim ahead of its attestation.
+        /// Any registered account other than the claimer can review it. Reviewing again replaces the earlier review
+        #[ink(message, payable)]
+        pub fn peer_review(
+            &mut self,
+            property_id: PropertyId,
+            approve: bool,
+            notes_ipfs_addr: ReviewNotesAddr,
+        ) -> Result<()> {
+            // get caller (which is the reviewer)
+            let caller = Self::env().caller();
+
+            if !self.accounts.contains(caller) {
+                return Err(Error::AccountNotFound);
+            }
+
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if property.claimer == caller {
+                return Err(Error::CannotReviewOwn);
+            }
+
+            let mut reviews = self.peer_reviews.get(&property_id).unwrap_or_default();
+            reviews.retain(|(reviewer, _, _)| *reviewer != caller);
+            reviews.push((caller, approve, notes_ipfs_addr.clone()));
+            self.peer_reviews.insert(&property_id, &reviews);
+
+            // emit event
+            self.env().emit_event(PeerReviewed {
+                reviewer: caller,
+                property_id,
+                approve,
+                notes_ipfs_addr,
+            });
+
+            Ok(())
+        }
+
+        /// Return the peer reviews of a claim
+        #[ink(message)]
+        pub fn peer_reviews(&self, property_id: PropertyId) -> Vec<PeerReview> {
+            self.peer_reviews.get(&property_id).unwrap_or_default()
+        }
+
+        /// Return whether a claim has at least one approving peer review
+        #[ink(message)]
+        pub fn is_peer_approved(&self, property_id: PropertyId) -> bool {
+            self.peer_reviews
+                .get(&property_id)
+                .unwrap_or_default()
+                .iter()
+                .any(|(_, approve, _)| *approve)
+        }
+
+        /// Return whether an account may sign documents of a property type.
+        /// It applies the same authorization as `sign_document`: a registrar of the type, or a deputy of its original registrar
+        #[ink(message)]
+        pub fn can_attest(&self, account_id: AccountId, property_type_id: PropertyTypeId) -> bool {
+            self.is_registrar(account_id, &property_type_id)
+                || (self.is_delegated_signer(account_id, &property_type_id)
+                    && self.property_type(&property_type_id).is_some())
+        }
+
+        /// Sign a property document and cement the owner as the undisputed rightful owner of the property.
+        /// It returns an error if the attested is unauthorized to attest ownership, or if the property does not exist.
+        /// Authorization is gotten by checking for equality between the account that created the property type and the attesting account.
+        /// The property only becomes attested once it has gathered the number of signatures its type requires
+        #[ink(message, payable)]
+        pub fn sign_document(
+            &mut self,
+            property_id: PropertyId,
+            property_type_id: PropertyTypeId,
+            assertion_timestamp: AssertionTimestamp,
+        ) -> Result<()> {
+            // get caller (which is the account making the attestation)
+            let caller = Self::env().caller();
+
+            // an empty timestamp would read as "not yet attested" once the threshold is met
+            if assertion_timestamp.is_empty() {
+                return Err(Error::EmptyAssertionTimestamp);
+            }
+
+            // check that only the authorized account can sign.
+            // That is a registrar of the type or a deputy the original registrar delegated to.
+            // An account that has registered no property types at all and is no deputy is never authorized
+            let registered = self
+                .registrations
+                .get(caller)
+                .unwrap_or_default()
+                .into_iter()
+                .find(|ptype| ptype.id == property_type_id);
+            let property_type = match registered {
+                Some(ptype) => ptype,
+                None if self.is_delegated_signer(caller, &property_type_id) => self
+                    .property_type(&property_type_id)
+                    .ok_or(Error::UnauthorizedAccount(caller))?,
+                // error! unauthorized
+                None => return Err(Error::UnauthorizedAccount(caller)),
+            };
+
+            // the number of signatures needed before the property is fully attested
+            let required_signatures = property_type.required_signatures.max(1);
+
+            // now sign document
+            let mut property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if property.disputed {
+                return Err(Error::PropertyDisputed);
+            }
+
+            // the caller is only an authority for the type the property is filed under
+            if property.property_type_id != property_type_id {
+                return Err(Error::TypeMismatch);
+            }
+
+            // a claimer can never attest their own claim, even as a registrar of its type
+            if property.claimer == caller {
+                return Err(Error::SelfAttestationForbidden);
+            }
+
+            // an authority signing again replaces its earlier signature
+            property.signatures.retain(|(signer, _)| *signer != caller);
+            property
+                .signatures
+                .push((caller, assertion_timestamp.clone()));
+
+            let threshold_met = property.signatures.len() >= required_signatures as usize;
+            let newly_attested = threshold_met && property.assertion.0.is_empty();
+            if threshold_met {
+                property.assertion = (assertion_timestamp.clone(), caller);
+            }
+
+            // update property
+            property.last_modified_block = self.env().block_number();
+            self.properties.insert(&property_id, &property);
+
+            // index the signature under the attester, once per property
+            let mut attested = self.attested_index.get(caller).unwrap_or_default();
+            if !attested.contains(&property_id) {
+                attested.push(property_id.clone());
+                self.attested_index.insert(caller, &attested);
+            }
+
+            // emit event
+            self.env().emit_event(PropertyDocumentSigned {
+                attester: caller,
+                property_id: property_id.clone(),
+            });
+
+            if newly_attested {
+                self.env().emit_event(PropertyFullyAttested {
+                    property_id,
+                    assertion_timestamp,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Sign many property documents in one call, with the same authorization and signing rules as `sign_document`.
+        /// The batch is all-or-nothing: if the caller may not sign any entry, or any entry fails, none of them are signed.
+        /// A batch can hold at most `MAX_BATCH_SIZE` entries
+        #[ink(message, payable)]
+        pub fn sign_documents_batch(
+            &mut self,
+            entries: Vec<(PropertyId, PropertyTypeId)>,
+            assertion_timestamp: AssertionTimestamp,
+        ) -> Result<()> {
+            if entries.len() > MAX_BATCH_SIZE {
+                return Err(Error::BatchTooLarge);
+            }
+
+            // an error reverts the signatures already made in this batch
+            for (property_id, property_type_id) in entries {
+                self.sign_document(property_id, property_type_id, assertion_timestamp.clone())?;
+            }
+
+            Ok(())
+        }
+
+        /// Return the properties an authority has signed
+        #[ink(message)]
+        pub fn properties_attested_by(&self, attester: AccountId) -> Vec<PropertyId> {
+            self.attested_index.get(attester).unwrap_or_default()
+        }
+
+        /// Return the authorities of a property type, ranked by how many properties of the type they have signed.
+        /// The authorities are the original registrar and its deputies.
+        /// Entries are separated by the '###' character, and an authority and its count by a '~' character
+        /// E.g authority1~12###authority2~3
+        #[ink(message)]
+        pub fn authority_leaderboard(&self, property_type_id: PropertyTypeId) -> Vec<u8> {
+            let mut authorities: Vec<AccountId> =
+                self.type_owner.get(&property_type_id).into_iter().collect();
+            for signer in self
+                .delegated_signers
+                .get(&property_type_id)
+                .unwrap_or_default()
+            {
+                if !authorities.contains(&signer) {
+                    authorities.push(signer);
+                }
+            }
+
+            // count the properties of the type each authority has signed
+            let mut leaderboard: Vec<(AccountId, u32)> = authorities
+                .into_iter()
+                .map(|authority| {
+                    let count = self
+                        .attested_index
+                        .get(authority)
+                        .unwrap_or_default()
+                        .iter()
+                        .filter(|property_id| {
+                            self.properties.get(*property_id).is_some_and(|property| {
+                                property.property_type_id == property_type_id
+                            })
+                        })
+                        .count() as u32;
+                    (authority, count)
+                })
+                .collect();
+            leaderboard.sort_by_key(|(_, count)| core::cmp::Reverse(*count));
+
+            leaderboard
+                .into_iter()
+                .flat_map(|(authority, count)| {
+                    let mut entry = self.convert_accountid_to_vec(&authority);
+                    entry.push(b'~');
+                    entry.extend(count.to_string().as_bytes());
+                    entry.extend("###".as_bytes()); // add separator
+                    entry.into_iter()
+                })
+                .collect()
+        }
+
+        /// Mark a property as disputed, freezing its transfer and attestation until the dispute is resolved.
+        /// Any registered account can raise a dispute
+        #[ink(message, payable)]
+        pub fn raise_dispute(
+            &mut self,
+            property_id: PropertyId,
+            reason_ipfs_addr: DisputeReasonAddr,
+        ) -> Result<()> {
+            // get caller (which is the account raising the dispute)
+            let caller = Self::env().caller();
+
+            if !self.accounts.contains(caller) {
+                return Err(Error::AccountNotFound);
+            }
+
+            let mut property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if property.disputed {
+                return Err(Error::PropertyDisputed);
+            }
+
+            property.disputed = true;
+            property.last_modified_block = self.env().block_number();
+            self.properties.insert(&property_id, &property);
+            self.disputes
+                .insert(&property_id, &(caller, reason_ipfs_addr.clone()));
+
+            // emit event
+            self.env().emit_event(DisputeRaised {
+                disputant: caller,
+                property_id,
+                reason_ipfs_addr,
+            });
+
+            Ok(())
+        }
+
+        /// Resolve the dispute on a property, allowing it to be transferred and attested again.
+        /// Only an authority that registered the property's type can resolve it
+        #[ink(message, payable)]
+        pub fn resolve_dispute(&mut self, property_id: PropertyId) -> Result<()> {
+            // get caller (which is the authority resolving the dispute)
+            let caller = Self::env().caller();
+
+            let mut property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if !self.is_registrar(caller, &property.property_type_id) {
+                return Err(Error::UnauthorizedAccount(caller));
+            }
+
+            property.disputed = false;
+            property.last_modified_block = self.env().block_number();
+            self.properties.insert(&property_id, &property);
+            self.disputes.remove(&property_id);
+
+            // emit event
+            self.env().emit_event(DisputeResolved {
+                authority: caller,
+                property_id,
+            });
+
+            Ok(())
+        }
+
+        /// Return the verification status of a property.
+        /// This verification status includes: 1. AccountIds showing transfer History 2. AssertionTimestamp
+        /// The accountId's showing transfer history are separated with a '$' character.
+        /// The history is separated from the timestamp by a '@' character
+        #[ink(message)]
+        pub fn attestation_status(&self, property_id: PropertyId) -> Vec<u8> {
+            // the vector we are returning, containing all the accountIds that have had possession of the property
+            let mut transfer_history = Vec::new();
+        
+            if let Some(property) = self.properties.get(&property_id) {
+                // we need to return AccountIdVec, hence we need to make the conversion
+                let history = self
+                    .transfer_histories
+                    .get(&property_id)
+                    .unwrap_or_default();
+                for (account_id, ..) in &history {
+                    transfer_history.push(self.convert_accountid_to_vec(account_id));
+                }
+        
+                // Flatten and concatenate the vectors in transfer_history
+                let mut flattened_history = Vec::new();
+                for inner_vec in &transfer_history {
+                    flattened_history.extend(inner_vec.iter().copied());
+                    flattened_history.push(b'$');
+                }
+        
+                // append the assertion timestamp to it
+                flattened_history.push(b'@');
+                flattened_history.extend(property.assertion.0.iter());
+                flattened_history
+            } else {
+                // 0 is the flag to indicate that the property has not been attested
+                Default::default()
+            }
+        }
+
+        /// Return the chain of custody and attestation of a property as a typed report.
+        /// This is the typed alternative to `attestation_status`. `None` is returned if the property does not exist
+        #[ink(message)]
+        pub fn verification_report(&self, property_id: PropertyId) -> Option<VerificationReport> {
+            let property = self.properties.get(&property_id)?;
+
+            let mut owners = Vec::new();
+            let mut transfer_times = Vec::new();
+            for (account_id, time, ..) in self
+                .transfer_histories
+                .get(&property_id)
+                .unwrap_or_default()
+            {
+                owners.push(account_id);
+                transfer_times.push(time);
+            }
+            owners.push(property.claimer);
+
+            Some(VerificationReport {
+                owners,
+                transfer_times,
+                attester: property.assertion.1,
+                attested_at: property.assertion.0,
+            })
+        }
+
+        /// Return every account that has owned a property, oldest first and ending with the current owner.
+        /// An empty vector is returned if the property does not exist
+        #[ink(message)]
+        pub fn owners_history(&self, property_id: PropertyId) -> Vec<AccountId> {
+            self.verification_report(property_id)
+                .map(|report| report.owners)
+                .unwrap_or_default()
+        }
+
+        /// Return the chain of custody of a property.
+        /// Each entry is the parsable account id of a previous owner, the time it transferred the property
+        /// and the IPFS address of the transfer document (empty if none was recorded)
+        #[ink(message)]
+        pub fn get_transfer_history(
+            &self,
+            property_id: PropertyId,
+        ) -> Vec<(AccountIdVec, PropertyTransferTimestamp, TransferDocAddr)> {
+            self.transfer_histories
+                .get(&property_id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(account_id, time, _, transfer_doc_ipfs_addr)| {
+                    (
+                        self.convert_accountid_to_vec(&account_id),
+                        time,
+                        transfer_doc_ipfs_addr,
+                    )
+                })
+                .collect()
+        }
+
+        /// Return the hash of the sale contract attached to an entry of the chain of custody of a property.
+        /// `None` is returned if the index is out of range; the zero hash means no contract was attached
+        #[ink(message)]
+        pub fn sale_contract_of(
+            &self,
+            property_id: PropertyId,
+            index: u32,
+        ) -> Option<SaleContractHash> {
+            self.transfer_histories
+                .get(&property_id)?
+                .get(index as usize)
+                .map(|(_, _, contract_hash, _)| *contract_hash)
+        }
+
+        /// Return the time a property last changed hands, or `None` if it was never transferred
+        #[ink(message)]
+        pub fn last_transfer_time(&self, property_id: PropertyId) -> Option<TimeString> {
+            self.transfer_histories
+                .get(&property_id)?
+                .pop()
+                .map(|(_, time, ..)| time)
+        }
+
+        /// Return the number of entries in the chain of custody of a property
+        #[ink(message)]
+        pub fn transfer_history_len(&self, property_id: PropertyId) -> u32 {
+            self.transfer_histories
+                .get(&property_id)
+                .unwrap_or_default()
+                .len() as u32
+        }
+
+        /// Return a single entry of the chain of custody of a property: the parsable account id of a previous owner
+        /// and the time it transferred the property. `None` is returned if the index is out of range
+        #[ink(message)]
+        pub fn owner_at(
+            &self,
+            property_id: PropertyId,
+            index: u32,
+        ) -> Option<(AccountIdVec, PropertyTransferTimestamp)> {
+            self.transfer_histories
+                .get(&property_id)?
+                .get(index as usize)
+                .map(|(account_id, time, ..)| {
+                    (self.convert_accountid_to_vec(account_id), time.clone())
+                })
+        }
+
+        /// Return the current owner and the originator (first claimer) of a property, separated by a '$' character.
+        /// A property that was never transferred has the same owner and originator.
+        /// An empty vector is returned if the property does not exist
+        #[ink(message)]
+        pub fn owner_and_originator(&self, property_id: PropertyId) -> Vec<u8> {
+            match self.properties.get(&property_id) {
+                Some(property) => {
+                    // the first entry of the chain of custody is the account that originally claimed the property
+                    let originator = self
+                        .transfer_histories
+                        .get(&property_id)
+                        .unwrap_or_default()
+                        .first()
+                        .map(|(account_id, ..)| *account_id)
+                        .unwrap_or(property.claimer);
+
+                    let mut return_vec = self.convert_accountid_to_vec(&property.claimer);
+                    return_vec.push(b'$');
+                    return_vec.extend(self.convert_accountid_to_vec(&originator));
+                    return_vec
+                }
+                None => Default::default(),
+            }
+        }
+
+        /// Return a Blake2x256 digest of the full on-chain record of a property.
+        /// The record covers its ownership, claim document, transfer history, signatures and flags,
+        /// so any lifecycle event changes the digest. The zero hash is returned if the property does not exist
+        #[ink(message)]
+        pub fn audit_hash(&self, property_id: PropertyId) -> [u8; 32] {
+            match self.properties.get(&property_id) {
+                Some(property) => {
+                    let transfer_history = self
+                        .transfer_histories
+                        .get(&property_id)
+                        .unwrap_or_default();
+                    self.env()
+                        .hash_encoded::<Blake2x256, _>(&(property, transfer_history))
+                }
+                None => [0u8; 32],
+            }
+        }
+
+        /// Return whether a property has been attested.
+        /// A property that does not exist is reported as unattested
+        #[ink(message)]
+        pub fn is_attested(&self, property_id: PropertyId) -> bool {
+            match self.properties.get(&property_id) {
+                Some(property) => !property.assertion.0.is_empty(),
+                None => false,
+            }
+        }
+
+        /// Return the parsable account id of the authority that attested a property.
+        /// An empty vector is returned if the property does not exist or is unattested
+        #[ink(message)]
+        pub fn attester_of(&self, property_id: PropertyId) -> AccountIdVec {
+            match self.properties.get(&property_id) {
+                Some(property) if !property.assertion.0.is_empty() => {
+                    self.convert_accountid_to_vec(&property.assertion.1)
+                }
+                _ => Default::default(),
+            }
+        }
+
+        /// Return the number of unique authorities that have signed a property document.
+        /// Zero is returned if the property does not exist
+        #[ink(message)]
+        pub fn distinct_attesters(&self, property_id: PropertyId) -> u32 {
+            let signatures = self
+                .properties
+                .get(&property_id)
+                .map(|property| property.signatures)
+                .unwrap_or_default();
+
+            let mut attesters: Vec<AccountId> = Vec::new();
+            for (signer, _) in signatures {
+                if !attesters.contains(&signer) {
+                    attesters.push(signer);
+                }
+            }
+
+            attesters.len() as u32
+        }
+
+        /// Record an insurance policy held on a property.
+        /// Only the owner of the property can record a policy against it
+        #[ink(message, payable)]
+        pub fn add_policy(
+            &mut self,
+            property_id: PropertyId,
+            policy_ipfs_addr: PolicyAddr,
+            expiry_block: BlockNumber,
+        ) -> Result<()> {
+            // get caller (which is the account recording the policy)
+            let caller = Self::env().caller();
+
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if property.claimer != caller {
+                return Err(Error::NotPropertyOwner);
+            }
+
+            let mut policies = self.policies.get(&property_id).unwrap_or_default();
+            policies.push((policy_ipfs_addr.clone(), expiry_block));
+            self.policies.insert(&property_id, &policies);
+
+            // emit event
+            self.env().emit_event(PolicyAdded {
+                property_id,
+                policy_ipfs_addr,
+                expiry_block,
+            });
+
+            Ok(())
+        }
+
+        /// Return the insurance policies on a property that have not yet expired
+        #[ink(message)]
+        pub fn active_policies(&self, property_id: PropertyId) -> Vec<Policy> {
+            let current_block = self.env().block_number();
+
+            self.policies
+                .get(&property_id)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(_, expiry_block)| *expiry_block > current_block)
+                .collect()
+        }
+
+        /// Grant an access right (such as a right of way) over a property to a beneficiary.
+        /// Only the owner of the property can grant an easement over it
+        #[ink(message, payable)]
+        pub fn grant_easement(
+            &mut self,
+            property_id: PropertyId,
+            beneficiary: AccountId,
+            terms_ipfs_addr: EasementTermsAddr,
+        ) -> Result<()> {
+            // get caller (which is the owner granting the easement)
+            let caller = Self::env().caller();
+
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if property.claimer != caller {
+                return Err(Error::NotPropertyOwner);
+            }
+
+            if !Self::is_plausible_cid(&terms_ipfs_addr) {
+                return Err(Error::InvalidCid);
+            }
+
+            let mut easements = self.easements.get(&property_id).unwrap_or_default();
+            easements.push((beneficiary, terms_ipfs_addr.clone()));
+            self.easements.insert(&property_id, &easements);
+
+            // emit event
+            self.env().emit_event(EasementGranted {
+                property_id,
+                beneficiary,
+                terms_ipfs_addr,
+            });
+
+            Ok(())
+        }
+
+        /// Return the easements granted over a property
+        #[ink(message)]
+        pub fn easements_of(&self, property_id: PropertyId) -> Vec<Easement> {
+            self.easements.get(&property_id).unwrap_or_default()
+        }
+
+        /// Record that the documents of a property were digitized from paper records.
+        /// Only an authority that registered the property's type can record it
+        #[ink(message, payable)]
+        pub fn mark_digitized(&mut self, property_id: PropertyId) -> Result<()> {
+            // get caller (which is the authority recording the digitization)
+            let caller = Self::env().caller();
+
+            let mut property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if !self.is_registrar(caller, &property.property_type_id) {
+                return Err(Error::UnauthorizedAccount(caller));
+            }
+
+            property.digitized = true;
+            property.digitized_by = caller;
+            property.last_modified_block = self.env().block_number();
+            self.properties.insert(&property_id, &property);
+
+            // emit event
+            self.env().emit_event(PropertyDigitized {
+                authority: caller,
+                property_id,
+            });
+
+            Ok(())
+        }
+
+        /// Return the authority that recorded a property's documents as digitized from paper.
+        /// `None` is returned if the property does not exist or was never digitized
+        #[ink(message)]
+        pub fn digitized_by(&self, property_id: PropertyId) -> Option<AccountId> {
+            self.properties
+                .get(&property_id)
+                .filter(|property| property.digitized)
+                .map(|property| property.digitized_by)
+        }
+
+        /// Set the official map tile/sheet reference of a property.
+        /// Only an authority that registered the property's type can set it
+        #[ink(message, payable)]
+        pub fn set_map_ref(&mut self, property_id: PropertyId, map_ref: Vec<u8>) -> Result<()> {
+            // get caller (which is the authority setting the reference)
+            let caller = Self::env().caller();
+
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if !self.is_registrar(caller, &property.property_type_id) {
+                return Err(Error::UnauthorizedAccount(caller));
+            }
+
+            self.map_refs.insert(&property_id, &map_ref);
+
+            // emit event
+            self.env().emit_event(MapReferenceSet {
+                authority: caller,
+                property_id,
+                map_ref,
+            });
+
+            Ok(())
+        }
+
+        /// Return the official map tile/sheet reference of a property.
+        /// An empty vector is returned if none has been set
+        #[ink(message)]
+        pub fn map_ref_of(&self, property_id: PropertyId) -> Vec<u8> {
+            self.map_refs.get(&property_id).unwrap_or_default()
+        }
+
+        /// Record a government charge (e.g unpaid rates) against a property.
+        /// Only an authority that registered the property's type can record it.
+        /// The property cannot be transferred until all its charges are cleared
+        #[ink(message, payable)]
+        pub fn add_government_charge(
+            &mut self,
+            property_id: PropertyId,
+            charge_ref: ChargeRef,
+            amount: Balance,
+        ) -> Result<()> {
+            // get caller (which is the authority levying the charge)
+            let caller = Self::env().caller();
+
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if !self.is_registrar(caller, &property.property_type_id) {
+                return Err(Error::UnauthorizedAccount(caller));
+            }
+
+            let mut charges = self
+                .government_charges
+                .get(&property_id)
+                .unwrap_or_default();
+            charges.push((charge_ref.clone(), amount));
+            self.government_charges.insert(&property_id, &charges);
+
+            // emit event
+            self.env().emit_event(GovernmentChargeAdded {
+                authority: caller,
+                property_id,
+                charge_ref,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Clear a settled government charge from a property.
+        /// Only an authority that registered the property's type can clear it
+        #[ink(message, payable)]
+        pub fn clear_government_charge(
+            &mut self,
+            property_id: PropertyId,
+            charge_ref: ChargeRef,
+        ) -> Result<()> {
+            // get caller (which is the authority clearing the charge)
+            let caller = Self::env().caller();
+
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if !self.is_registrar(caller, &property.property_type_id) {
+                return Err(Error::UnauthorizedAccount(caller));
+            }
+
+            let mut charges = self
+                .government_charges
+                .get(&property_id)
+                .unwrap_or_default();
+            charges.retain(|(reference, _)| reference != &charge_ref);
+            self.government_charges.insert(&property_id, &charges);
+
+            // emit event
+            self.env().emit_event(GovernmentChargeCleared {
+                authority: caller,
+                property_id,
+                charge_ref,
+            });
+
+            Ok(())
+        }
+
+        /// Return the outstanding government charges on a property
+        #[ink(message)]
+        pub fn government_charges(&self, property_id: PropertyId) -> Vec<GovernmentCharge> {
+            self.government_charges
+                .get(&property_id)
+                .unwrap_or_default()
+        }
+
+        /// Set the fee that must be paid to register a property type.
+        /// Only the contract owner can set it
+        #[ink(message, payable)]
+        pub fn set_ptype_fee(&mut self, fee: Balance) -> Result<()> {
+            self.ensure_owner()?;
+            self.ptype_fee = fee;
+
+            Ok(())
+        }
+
+        /// Return the fee that must be paid to register a property type
+        #[ink(message)]
+        pub fn ptype_fee(&self) -> Balance {
+            self.ptype_fee
+        }
+
+        /// Return the fees collected and not yet withdrawn
+        #[ink(message)]
+        pub fn collected_fees(&self) -> Balance {
+            self.collected_fees
+        }
+
+        /// Send all the collected fees to an account.
+        /// Only the contract owner can withdraw them
+        #[ink(message, payable)]
+        pub fn withdraw_fees(&mut self, to: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+
+            let amount = self.collected_fees;
+
+            // the fees stay on record if the transfer fails, so they can be withdrawn later.
+            // The contract does not allow reentrancy, so nothing can withdraw them twice in the meantime
+            if self.env().transfer(to, amount).is_err() {
+                return Err(Error::TransferFailed);
+            }
+            self.collected_fees = 0;
+
+            // emit event
+            self.env().emit_event(FeesWithdrawn { to, amount });
+
+            Ok(())
+        }
+
+        /// Record a mortgage on a property in favour of a lender.
+        /// Only the owner of the property can mortgage it, and it cannot be transferred until the mortgage is paid off
+        #[ink(message, payable)]
+        pub fn create_mortgage(
+            &mut self,
+            property_id: PropertyId,
+            lender: AccountId,
+            principal: Balance,
+            doc_ipfs: Vec<u8>,
+        ) -> Result<()> {
+            // get caller (which is the owner of the property)
+            let caller = Self::env().caller();
+
+            let property = self
+                .properties
+                .get(&property_id)
+                .ok_or_else(|| Error::PropertyNotFound(property_id.clone()))?;
+            if property.claimer != caller {
+                return Err(Error::NotPropertyOwner);
+            }
+
+            // a property can only carry one mortgage at a time
+            if self.mortgages.contains(&property_id) {
+                return Err(Error::PropertyEncumbered);
+            }
+
+            let mortgage = Mortgage {
+                lender,
+                principal,
+                repaid: 0,
+                doc_ipfs,
+            };
+            self.mortgages.insert(&property_id, &mortgage);
+
+            // emit event
+            self.env().emit_event(MortgageCreated {
+                lender,
+                property_id,
+                principal,
+            });
+
+            Ok(())
+        }
+
+        /// Repay the mortgage on a property with the value sent along with the call.
+        /// The repayment is forwarded to the lender, and the mortgage is released once it is paid off
+        #[ink(message, payable)]
+        pub fn repay_mortgage(&mut self, property_id: PropertyId) -> Result<()> {
+            // get caller (which is the account repaying)
+            let caller = Self::env().caller();
+            let amount = self.env().transferred_value();
+
+            let mut mortgage = self.mortgages.get(&property_id).ok_or(Error::NoMortgage)?;
+
+            if self.env().transfer(mortgage.lender, amount).is_err() {
+                return Err(Error::TransferFailed);
+            }
+            mortgage.repaid = mortgage.repaid.saturating_add(amount);
+
+            // emit event
+            self.env().emit_event(MortgageRepaid {
+                payer: caller,
+                property_id: property_id.clone(),
+                amount,
+                repaid: mortgage.repaid,
+            });
+
+            if mortgage.repaid >= mortgage.principal {
+                // paid off
+                self.mortgages.remove(&property_id);
+
+                self.env().emit_event(MortgageReleased {
+                    lender: mortgage.lender,
+                    property_id,
+                });
+            } else {
+                self.mortgages.insert(&property_id, &mortgage);
+            }
+
+            Ok(())
+        }
+
+        /// Return the active mortgage on a property, if any
+        #[ink(message)]
+        pub fn mortgage_of(&self, property_id: PropertyId) -> Option<Mortgage> {
+            self.mortgages.get(&property_id)
+        }
+
+        /// Return the code hash of the deployed contract so clients can verify the code they are talking to.
+        /// Off-chain (e.g in unit tests) there is no code hash and the default hash is returned
+        #[ink(message)]
+        pub fn self_code_hash(&self) -> Hash {
+            self.env().own_code_hash().unwrap_or_default()
+        }
+
+        /// Replace the code of the contract, keeping its storage.
+        /// Only the contract owner can upgrade the contract. Run `migrate` afterwards if the schema version changed
+        #[ink(message, payable)]
+        pub fn set_code(&mut self, code_hash: Hash) -> Result<()> {
+            self.ensure_owner()?;
+
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| Error::SetCodeFailed)
+        }
+
+        /// Return the storage schema version this code expects
+        #[ink(message)]
+        pub fn version(&self) -> u32 {
+            CONTRACT_VERSION
+        }
+
+        /// Return the storage schema version the contract storage is currently laid out in
+        #[ink(message)]
+        pub fn schema_version(&self) -> u32 {
+            self.schema_version
+        }
+
+        /// Bring the contract storage up to the schema version this code expects, after an upgrade.
+        /// Only the contract owner can run a migration. There is nothing to convert yet, so only the version is bumped
+        #[ink(message, payable)]
+        pub fn migrate(&mut self) -> Result<()> {
+            self.ensure_owner()?;
+
+            let from = self.schema_version;
+            if from < CONTRACT_VERSION {
+                // migration steps for each older schema version go here
+                self.schema_version = CONTRACT_VERSION;
+
+                // emit event
+                self.env().emit_event(Migrated {
+                    from,
+                    to: CONTRACT_VERSION,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Helper function to convert an AccountId into an AccountIdvec.
+        /// It uses the account_ids mapping property of our contract storage
+        pub fn convert_accountid_to_vec(&self, account_id: &AccountId) -> AccountIdVec {
+            if let Some(account_id_vec) = self.account_ids.get(account_id) {
+                account_id_vec
+            } else {
+                Default::default()
+            }
+        }
+
+        /// Helper function to make sure the caller is the contract owner
+        fn ensure_owner(&self) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+
+            Ok(())
+        }
+
+        /// Helper function to escape the separator characters of the string encodings with a backslash
+        fn escape(bytes: &[u8]) -> Vec<u8> {
+            let mut escaped = Vec::with_capacity(bytes.len());
+            for byte in bytes {
+                if matches!(byte, b'~' | b'#' | b'\\') {
+                    escaped.push(b'\\');
+                }
+                escaped.push(*byte);
+            }
+
+            escaped
+        }
+
+        /// Helper function to check that an account name is between 1 and `MAX_NAME_LENGTH` bytes long
+        fn is_valid_name(name: &[u8]) -> bool {
+            (1..=MAX_NAME_LENGTH).contains(&name.len())
+        }
+
+        /// Helper function to check whether an account registered a property type
+        fn is_registrar(&self, account_id: AccountId, property_type_id: &PropertyTypeId) -> bool {
+            self.registrations
+                .get(account_id)
+                .unwrap_or_default()
+                .iter()
+                .any(|ptype| &ptype.id == property_type_id)
+        }
+
+        /// Helper function to check whether an account is a deputy signer of a property type
+        fn is_delegated_signer(
+            &self,
+            account_id: AccountId,
+            property_type_id: &PropertyTypeId,
+        ) -> bool {
+            self.delegated_signers
+                .get(property_type_id)
+                .is_some_and(|signers| signers.contains(&account_id))
+        }
+
+        /// Helper function to look up a property type as registered by its original registrar
+        fn property_type(&self, property_type_id: &PropertyTypeId) -> Option<PropertyType> {
+            let registrar = self.type_owner.get(property_type_id)?;
+            self.registrations
+                .get(registrar)
+                .unwrap_or_default()
+                .into_iter()
+                .find(|ptype| &ptype.id == property_type_id)
+        }
+
+        /// Helper function to check that the value sent with a transfer covers the transfer fee of the property's type,
+        /// and to pay it to the registrar of the type. Without a registrar the value is kept with the collected fees
+        fn pay_transfer_fee(&mut self, property_type_id: &PropertyTypeId) -> Result<()> {
+            let paid = self.env().transferred_value();
+            let transfer_fee = self
+                .property_type(property_type_id)
+                .map(|ptype| ptype.transfer_fee)
+                .unwrap_or_default();
+            if paid < transfer_fee {
+                return Err(Error::InsufficientFee);
+            }
+
+            if paid > 0 {
+                match self.type_owner.get(property_type_id) {
+                    Some(registrar) => {
+                        if self.env().transfer(registrar, paid).is_err() {
+                            return Err(Error::TransferFailed);
+                        }
+                    }
+                    None => self.collected_fees = self.collected_fees.saturating_add(paid),
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Helper function to check whether a property has outstanding charges or a mortgage that block its transfer
+        fn is_encumbered(&self, property_id: &PropertyId) -> bool {
+            self.mortgages.contains(property_id)
+                || self
+                    .government_charges
+                    .get(property_id)
+                    .is_some_and(|charges| !charges.is_empty())
+        }
+
+        /// Helper function to record an (unattested) claim to a property and announce it.
+        /// Callers make sure the property id is not on record yet
+        fn file_claim(
+            &mut self,
+            claimer: AccountId,
+            property_type_id: PropertyTypeId,
+            property_id: PropertyId,
+            claim_ipfs_addr: PropertyClaimAddr,
+            office: Office,
+        ) {
+            // create a new property document
+            let property = Property {
+                claimer,
+                property_claim_addr: claim_ipfs_addr,
+                property_type_id: property_type_id.clone(),
+                // the claimer's address is the default value for the id of the asserting authority
+                // this is not a bug as the assertion flag will be the timestamp of the signing of the document
+                assertion: (Default::default(), claimer),
+                signatures: Vec::new(),
+                disputed: false,
+                office,
+                attributes: Vec::new(),
+                // like the assertion, the claimer is only a placeholder until the property is digitized
+                digitized: false,
+                digitized_by: claimer,
+                claimed_at: self.env().block_number(),
+                last_modified_block: self.env().block_number(),
+                share_bps: FULL_SHARE_BPS,
+            };
+
+            // register property under type of claim
+            self.index_claim(&property_type_id, &property_id);
+
+            // record the claimer as the owner
+            self.index_owner(claimer, &property_id);
+
+            // register (unattested) property claim onchain
+            self.total_properties = self.total_properties.saturating_add(1);
+            self.properties.insert(property_id.clone(), &property);
+
+            // a fresh claim starts its own chain of custody, and leaves nothing for an earlier owner to reverse
+            self.transfer_histories.remove(&property_id);
+            self.reversible_transfers.remove(&property_id);
+
+            // Emit event
+            self.env().emit_event(PropertyClaimRegistered {
+                claimer,
+                property_type_id,
+                property_id,
+            });
+        }
+
+        /// Helper function to add a property id to the list of claims registered under a property type.
+        /// It does nothing if the id is already there
+        fn index_claim(&mut self, property_type_id: &PropertyTypeId, property_id: &PropertyId) {
+            let mut property_ids = self.claims.get(property_type_id).unwrap_or_default();
+            if !property_ids.contains(property_id) {
+                property_ids.push(property_id.clone());
+                self.claims.insert(property_type_id, &property_ids);
+                self.total_claims = self.total_claims.saturating_add(1);
+            }
+        }
+
+        /// Helper function to remove a property id from the list of claims registered under a property type
+        fn unindex_claim(&mut self, property_type_id: &PropertyTypeId, property_id: &PropertyId) {
+            if let Some(mut property_ids) = self.claims.get(property_type_id) {
+                let claim_count = property_ids.len();
+                property_ids.retain(|id| id != property_id);
+                if property_ids.len() < claim_count {
+                    self.total_claims = self.total_claims.saturating_sub(1);
+                }
+                self.claims.insert(property_type_id, &property_ids);
+            }
+        }
+
+        /// Helper function to add a property id to the properties an account owns.
+        /// It does nothing if the id is already there
+        fn index_owner(&mut self, owner: AccountId, property_id: &PropertyId) {
+            let mut property_ids = self.owned_properties.get(owner).unwrap_or_default();
+            if !property_ids.contains(property_id) {
+                property_ids.push(property_id.clone());
+                self.owned_properties.insert(owner, &property_ids);
+            }
+        }
+
+        /// Helper function to remove a property id from the properties an account owns
+        fn unindex_owner(&mut self, owner: AccountId, property_id: &PropertyId) {
+            if let Some(mut property_ids) = self.owned_properties.get(owner) {
+                property_ids.retain(|id| id != property_id);
+                self.owned_properties.insert(owner, &property_ids);
+            }
+        }
+
+        /// Helper function to remove a property id from the properties each of its signers has signed
+        fn unindex_signers(&mut self, property: &Property, property_id: &PropertyId) {
+            for (signer, _) in &property.signatures {
+                if let Some(mut property_ids) = self.attested_index.get(signer) {
+                    property_ids.retain(|id| id != property_id);
+                    self.attested_index.insert(signer, &property_ids);
+                }
+            }
+        }
+
+        /// Helper function to delete a property along with everything recorded against it,
+        /// so nothing is left behind for a later claim to the id
+        fn purge_property(&mut self, property: &Property, property_id: &PropertyId) {
+            // its claim, record, history and any pending plan or reversal
+            self.unindex_claim(&property.property_type_id, property_id);
+            self.unindex_owner(property.claimer, property_id);
+            self.unindex_signers(property, property_id);
+            self.properties.remove(property_id);
+            self.transfer_histories.remove(property_id);
+            self.reversible_transfers.remove(property_id);
+            self.subdivisions.remove(property_id);
+            self.total_properties = self.total_properties.saturating_sub(1);
+
+            // and its encumbrances, flags, references and reviews
+            self.mortgages.remove(property_id);
+            self.government_charges.remove(property_id);
+            self.disputes.remove(property_id);
+            self.policies.remove(property_id);
+            self.easements.remove(property_id);
+            self.map_refs.remove(property_id);
+            self.peer_reviews.remove(property_id);
+            if let Some(external_id) = self.imported_from.take(property_id) {
+                self.external_ids.remove(&external_id);
+            }
+        }
+
+        /// Helper function to announce that a property changed hands
+        fn record_transfer(
+            &self,
+            sender: AccountId,
+            recipient: AccountId,
+            property_id: PropertyId,
+            kind: TransferKind,
+        ) {
+            self.env().emit_event(PropertyTransferred {
+                sender,
+                recipient,
+                property_id,
+                kind,
+            });
+        }
+
+        /// Helper function to sanity check an IPFS address.
+        /// It accepts base58 CIDv0s (`Qm...`, 46 bytes) and base32 CIDv1s (`b...`).
+        /// It is only meant to catch obvious typos, not to fully decode the CID
+        fn is_plausible_cid(addr: &[u8]) -> bool {
+            match addr {
+                [b'Q', b'm', ..] => {
+                    addr.len() == 46 && addr.iter().all(|byte| byte.is_ascii_alphanumeric())
+                }
+                [b'b', rest @ ..] => {
+                    (8..=128).contains(&addr.len())
+                        && rest
+                            .iter()
+                            .all(|byte| matches!(byte, b'a'..=b'z' | b'2'..=b'7'))
+                }
+                _ => false,
+            }
+        }
+    }
+
+    impl PropertyRegistry for Delphi {
+        #[ink(message)]
+        fn owner_of(&self, property_id: PropertyId) -> Option<AccountId> {
+            self.claimer_of(property_id)
+        }
+
+        #[ink(message)]
+        fn is_attested(&self, property_id: PropertyId) -> bool {
+            Delphi::is_attested(self, property_id)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test::DefaultAccounts;
+        use ink::env::DefaultEnvironment;
+
+        /// A well-formed CIDv0 to stand in for every document address
+        const CID: &[u8] = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+        fn accounts() -> DefaultAccounts<DefaultEnvironment> {
+            ink::env::test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<DefaultEnvironment>(caller);
+        }
+
+        /// Deploy the contract with alice, bob and charlie registered, bob as the registrar of the `land` type
+        /// and alice holding an unattested claim to `plot1`
+        fn setup() -> Delphi {
+            let accounts = accounts();
+            set_caller(accounts.alice);
+            let mut delphi = Delphi::new();
+            for (account_id, name) in [
+                (accounts.alice, "alice"),
+                (accounts.bob, "bob"),
+                (accounts.charlie, "charlie"),
+            ] {
+                set_caller(account_id);
+                delphi
+                    .register_account(
+                        name.as_bytes().to_vec(),
+                        name.as_bytes().to_vec(),
+                        b"0".to_vec(),
+                    )
+                    .unwrap();
+            }
+
+            set_caller(accounts.bob);
+            register_type(&mut delphi, b"land", 1).unwrap();
+
+            set_caller(accounts.alice);
+            claim(&mut delphi, b"land", b"plot1").unwrap();
+
+            delphi
+        }
+
+        /// Register a property type, free of any transfer fee, with the caller as its registrar
+        fn register_type(
+            delphi: &mut Delphi,
+            property_type_id: &[u8],
+            required_signatures: u8,
+        ) -> Result<()> {
+            delphi.register_ptype(
+                property_type_id.to_vec(),
+                CID.to_vec(),
+                required_signatures,
+                0,
+            )
+        }
+
+        /// File the caller's claim to a property, without naming an office
+        fn claim(delphi: &mut Delphi, property_type_id: &[u8], property_id: &[u8]) -> Result<()> {
+            delphi.register_claim(
+                property_type_id.to_vec(),
+                property_id.to_vec(),
+                CID.to_vec(),
+                Vec::new(),
+            )
+        }
+
+        /// Transfer `plot1` as a whole from its owner to `recipient`
+        fn transfer(delphi: &mut Delphi, recipient: AccountId) -> Result<()> {
+            delphi.transfer_whole(
+                b"plot1".to_vec(),
+                recipient,
+                CID.to_vec(),
+                b"1".to_vec(),
+                [0u8; 32],
+                Vec::new(),
+            )
+        }
+
+        /// Split `plot1` between its owner and `recipient`, in two even halves
+        fn split(
+            delphi: &mut Delphi,
+            recipient: AccountId,
+            senders_property_id: &[u8],
+            recipients_property_id: &[u8],
+        ) -> Result<()> {
+            delphi.split_and_transfer(
+                b"plot1".to_vec(),
+                recipient,
+                CID.to_vec(),
+                senders_property_id.to_vec(),
+                CID.to_vec(),
+                recipients_property_id.to_vec(),
+                b"1".to_vec(),
+                FULL_SHARE_BPS / 2,
+                FULL_SHARE_BPS / 2,
+                Vec::new(),
+            )
+        }
+
+        /// Have bob, the registrar of `land`, approve the subdivision of a property
+        fn approve_subdivision(delphi: &mut Delphi, property_id: &[u8]) {
+            set_caller(accounts().bob);
+            delphi
+                .approve_subdivision(property_id.to_vec(), CID.to_vec())
+                .unwrap();
+        }
+
+        #[ink::test]
+        fn type_claims_root_changes_with_the_claims() {
+            let mut delphi = setup();
+
+            assert_eq!(delphi.type_claims_root(b"farm".to_vec()), [0u8; 32]);
+
+            let hash = |input: &[u8]| {
+                let mut output = [0u8; 32];
+                ink::env::hash_bytes::<Blake2x256>(input, &mut output);
+                output
+            };
+
+            // a single claim is its own (prefixed) leaf
+            let plot1 = hash(&[&[0x00][..], b"plot1"].concat());
+            assert_eq!(delphi.type_claims_root(b"land".to_vec()), plot1);
+
+            // two claims hash into a (prefixed) node
+            claim(&mut delphi, b"land", b"plot2").unwrap();
+            let plot2 = hash(&[&[0x00][..], b"plot2"].concat());
+            assert_eq!(
+                delphi.type_claims_root(b"land".to_vec()),
+                hash(&[&[0x01][..], &plot1, &plot2].concat())
+            );
+        }
+
+        #[ink::test]
+        fn properties_exist_works() {
+            let delphi = setup();
+
+            assert_eq!(
+                delphi.properties_exist(vec![b"plot9".to_vec(), b"plot1".to_vec(), Vec::new()]),
+                vec![false, true, false]
+            );
+        }
+
+        #[ink::test]
+        fn expired_policies_are_not_active() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                delphi.add_policy(b"plot1".to_vec(), b"policy0".to_vec(), 100),
+                Err(Error::NotPropertyOwner)
+            );
+
+            set_caller(accounts.alice);
+            delphi
+                .add_policy(b"plot1".to_vec(), b"policy1".to_vec(), 2)
+                .unwrap();
+            delphi
+                .add_policy(b"plot1".to_vec(), b"policy2".to_vec(), 100)
+                .unwrap();
+            assert_eq!(delphi.active_policies(b"plot1".to_vec()).len(), 2);
+
+            for _ in 0..2 {
+                ink::env::test::advance_block::<DefaultEnvironment>();
+            }
+            assert_eq!(
+                delphi.active_policies(b"plot1".to_vec()),
+                vec![(b"policy2".to_vec(), 100)]
+            );
+        }
+
+        #[ink::test]
+        fn accounts_without_registrations_cannot_sign() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                delphi.sign_document(b"plot1".to_vec(), b"land".to_vec(), b"1".to_vec()),
+                Err(Error::UnauthorizedAccount(accounts.charlie))
+            );
+            assert!(!delphi.is_attested(b"plot1".to_vec()));
+        }
+
+        #[ink::test]
+        fn transfer_to_new_creates_the_recipients_account() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            let transfer_to_django = |delphi: &mut Delphi| {
+                delphi.transfer_to_new(
+                    b"plot1".to_vec(),
+                    accounts.django,
+                    b"django".to_vec(),
+                    b"django-id".to_vec(),
+                    CID.to_vec(),
+                    b"1".to_vec(),
+                    [0u8; 32],
+                    Vec::new(),
+                )
+            };
+
+            // only the owner can make the transfer
+            set_caller(accounts.charlie);
+            assert_eq!(
+                transfer_to_django(&mut delphi),
+                Err(Error::NotPropertyOwner)
+            );
+            assert_eq!(delphi.account_name(accounts.django), None);
+
+            set_caller(accounts.alice);
+            assert_eq!(transfer_to_django(&mut delphi), Ok(()));
+            assert_eq!(
+                delphi.account_name(accounts.django),
+                Some(b"django".to_vec())
+            );
+            assert_eq!(delphi.claimer_of(b"plot1".to_vec()), Some(accounts.django));
+            assert_eq!(delphi.total_accounts(), 4);
+        }
+
+        #[ink::test]
+        fn set_map_ref_works() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            assert!(delphi.map_ref_of(b"plot1".to_vec()).is_empty());
+
+            // only an authority of the property's type can set the reference
+            set_caller(accounts.alice);
+            assert_eq!(
+                delphi.set_map_ref(b"plot1".to_vec(), b"sheet-4".to_vec()),
+                Err(Error::UnauthorizedAccount(accounts.alice))
+            );
+
+            set_caller(accounts.bob);
+            delphi
+                .set_map_ref(b"plot1".to_vec(), b"sheet-4".to_vec())
+                .unwrap();
+            assert_eq!(delphi.map_ref_of(b"plot1".to_vec()), b"sheet-4".to_vec());
+        }
+
+        #[ink::test]
+        fn splits_keep_the_chain_of_custody() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.alice);
+            transfer(&mut delphi, accounts.bob).unwrap();
+            set_caller(accounts.bob);
+            transfer(&mut delphi, accounts.charlie).unwrap();
+
+            approve_subdivision(&mut delphi, b"plot1");
+            set_caller(accounts.charlie);
+            split(&mut delphi, accounts.alice, b"plot1a", b"plot1b").unwrap();
+
+            for property_id in [b"plot1a", b"plot1b"] {
+                assert_eq!(
+                    delphi.owners_history(property_id.to_vec())[..3],
+                    [accounts.alice, accounts.bob, accounts.charlie]
+                );
+                assert_eq!(delphi.transfer_history_len(property_id.to_vec()), 3);
+            }
+        }
+
+        #[ink::test]
+        fn splits_need_an_approved_subdivision() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.alice);
+            assert_eq!(
+                split(&mut delphi, accounts.bob, b"plot1a", b"plot1b"),
+                Err(Error::SubdivisionNotApproved)
+            );
+
+            approve_subdivision(&mut delphi, b"plot1");
+            set_caller(accounts.alice);
+            assert_eq!(
+                split(&mut delphi, accounts.bob, b"plot1a", b"plot1b"),
+                Ok(())
+            );
+            assert!(!delphi.property_exists(b"plot1".to_vec()));
+            assert_eq!(delphi.claimer_of(b"plot1a".to_vec()), Some(accounts.alice));
+            assert_eq!(delphi.claimer_of(b"plot1b".to_vec()), Some(accounts.bob));
+        }
+
+        #[ink::test]
+        fn every_account_has_a_parsable_id() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            // a transfer to a newcomer is the other path that creates an account
+            set_caller(accounts.alice);
+            delphi
+                .transfer_to_new(
+                    b"plot1".to_vec(),
+                    accounts.django,
+                    b"django".to_vec(),
+                    b"django-id".to_vec(),
+                    CID.to_vec(),
+                    b"1".to_vec(),
+                    [0u8; 32],
+                    Vec::new(),
+                )
+                .unwrap();
+
+            for account_id in [
+                accounts.alice,
+                accounts.bob,
+                accounts.charlie,
+                accounts.django,
+            ] {
+                assert!(delphi.accounts.contains(account_id));
+                assert!(delphi.account_ids.contains(account_id));
+            }
+            assert_eq!(delphi.claimer_of(b"plot1".to_vec()), Some(accounts.django));
+            assert_eq!(delphi.claimer_of(b"plot9".to_vec()), None);
+        }
+
+        #[ink::test]
+        fn government_charges_block_transfers_until_cleared() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.bob);
+            delphi
+                .add_government_charge(b"plot1".to_vec(), b"rates-2024".to_vec(), 50)
+                .unwrap();
+            delphi
+                .add_government_charge(b"plot1".to_vec(), b"rates-2025".to_vec(), 70)
+                .unwrap();
+            assert_eq!(
+                delphi.government_charges(b"plot1".to_vec()),
+                vec![(b"rates-2024".to_vec(), 50), (b"rates-2025".to_vec(), 70)]
+            );
+
+            set_caller(accounts.alice);
+            assert_eq!(
+                transfer(&mut delphi, accounts.charlie),
+                Err(Error::PropertyEncumbered)
+            );
+
+            set_caller(accounts.bob);
+            for charge_ref in [b"rates-2024", b"rates-2025"] {
+                delphi
+                    .clear_government_charge(b"plot1".to_vec(), charge_ref.to_vec())
+                    .unwrap();
+            }
+            assert!(delphi.government_charges(b"plot1".to_vec()).is_empty());
+
+            set_caller(accounts.alice);
+            assert_eq!(transfer(&mut delphi, accounts.charlie), Ok(()));
+        }
+
+        #[ink::test]
+        fn register_ptype_charges_the_registration_fee() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            // only the contract owner can set the fee
+            set_caller(accounts.bob);
+            assert_eq!(delphi.set_ptype_fee(10), Err(Error::NotOwner));
+            set_caller(accounts.alice);
+            delphi.set_ptype_fee(10).unwrap();
+
+            set_caller(accounts.charlie);
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(9);
+            assert_eq!(
+                register_type(&mut delphi, b"farm", 1),
+                Err(Error::InsufficientFee)
+            );
+            assert_eq!(delphi.collected_fees(), 0);
+
+            // an overpayment is kept in full
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(15);
+            assert_eq!(register_type(&mut delphi, b"farm", 1), Ok(()));
+            assert_eq!(delphi.collected_fees(), 15);
+        }
+
+        #[ink::test]
+        fn reannounce_claim_leaves_the_claim_untouched() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                delphi.reannounce_claim(b"plot1".to_vec()),
+                Err(Error::NotOwner)
+            );
+
+            set_caller(accounts.alice);
+            let events = ink::env::test::recorded_events().count();
+            let audit_hash = delphi.audit_hash(b"plot1".to_vec());
+            delphi.reannounce_claim(b"plot1".to_vec()).unwrap();
+            assert_eq!(ink::env::test::recorded_events().count(), events + 1);
+            assert_eq!(delphi.audit_hash(b"plot1".to_vec()), audit_hash);
+            assert_eq!(delphi.total_properties(), 1);
+        }
+
+        #[ink::test]
+        fn only_the_owner_can_transfer() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                transfer(&mut delphi, accounts.bob),
+                Err(Error::NotPropertyOwner)
+            );
+            assert_eq!(delphi.claimer_of(b"plot1".to_vec()), Some(accounts.alice));
+
+            set_caller(accounts.alice);
+            assert_eq!(transfer(&mut delphi, accounts.bob), Ok(()));
+            assert_eq!(delphi.claimer_of(b"plot1".to_vec()), Some(accounts.bob));
+            assert_eq!(delphi.properties_count_by_owner(accounts.alice), 0);
+            assert_eq!(delphi.properties_count_by_owner(accounts.bob), 1);
+        }
+
+        #[ink::test]
+        fn transfers_of_missing_properties_fail() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.alice);
+            assert_eq!(
+                delphi.transfer_whole(
+                    b"plot9".to_vec(),
+                    accounts.bob,
+                    CID.to_vec(),
+                    b"1".to_vec(),
+                    [0u8; 32],
+                    Vec::new(),
+                ),
+                Err(Error::PropertyNotFound(b"plot9".to_vec()))
+            );
+            assert_eq!(
+                delphi.split_and_transfer(
+                    b"plot9".to_vec(),
+                    accounts.bob,
+                    CID.to_vec(),
+                    b"plot9a".to_vec(),
+                    CID.to_vec(),
+                    b"plot9b".to_vec(),
+                    b"1".to_vec(),
+                    FULL_SHARE_BPS / 2,
+                    FULL_SHARE_BPS / 2,
+                    Vec::new(),
+                ),
+                Err(Error::PropertyNotFound(b"plot9".to_vec()))
+            );
+            assert!(!delphi.property_exists(b"plot9a".to_vec()));
+            assert_eq!(delphi.transfer_history_len(b"plot9".to_vec()), 0);
+        }
+
+        #[ink::test]
+        fn office_of_works() {
+            let mut delphi = setup();
+
+            delphi
+                .register_claim(
+                    b"land".to_vec(),
+                    b"plot2".to_vec(),
+                    CID.to_vec(),
+                    b"office".to_vec(),
+                )
+                .unwrap();
+            assert_eq!(delphi.office_of(b"plot2".to_vec()), b"office".to_vec());
+            assert!(delphi.office_of(b"plot1".to_vec()).is_empty());
+            assert!(delphi.office_of(b"plot9".to_vec()).is_empty());
+        }
+
+        #[ink::test]
+        fn audit_hash_changes_with_the_lifecycle() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            assert_eq!(delphi.audit_hash(b"plot9".to_vec()), [0u8; 32]);
+            let claimed = delphi.audit_hash(b"plot1".to_vec());
+            assert_ne!(claimed, [0u8; 32]);
+
+            set_caller(accounts.alice);
+            transfer(&mut delphi, accounts.charlie).unwrap();
+            let transferred = delphi.audit_hash(b"plot1".to_vec());
+            assert_ne!(transferred, claimed);
+
+            set_caller(accounts.bob);
+            delphi
+                .sign_document(b"plot1".to_vec(), b"land".to_vec(), b"1".to_vec())
+                .unwrap();
+            assert_ne!(delphi.audit_hash(b"plot1".to_vec()), transferred);
+        }
+
+        #[ink::test]
+        fn peer_review_works() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.alice);
+            assert_eq!(
+                delphi.peer_review(b"plot1".to_vec(), true, CID.to_vec()),
+                Err(Error::CannotReviewOwn)
+            );
+            assert!(!delphi.is_peer_approved(b"plot1".to_vec()));
+
+            // reviewing again replaces the earlier review
+            set_caller(accounts.charlie);
+            delphi
+                .peer_review(b"plot1".to_vec(), false, CID.to_vec())
+                .unwrap();
+            delphi
+                .peer_review(b"plot1".to_vec(), true, CID.to_vec())
+                .unwrap();
+            assert_eq!(
+                delphi.peer_reviews(b"plot1".to_vec()),
+                vec![(accounts.charlie, true, CID.to_vec())]
+            );
+            assert!(delphi.is_peer_approved(b"plot1".to_vec()));
+        }
+
+        #[ink::test]
+        fn empty_ids_are_rejected() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                register_type(&mut delphi, b"", 1),
+                Err(Error::EmptyPropertyId)
+            );
+
+            set_caller(accounts.alice);
+            assert_eq!(
+                claim(&mut delphi, b"land", b""),
+                Err(Error::EmptyPropertyId)
+            );
+            assert_eq!(
+                delphi.transfer_property(
+                    Vec::new(),
+                    accounts.bob,
+                    CID.to_vec(),
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                    b"1".to_vec(),
+                    Vec::new(),
+                ),
+                Err(Error::EmptyPropertyId)
+            );
+
+            approve_subdivision(&mut delphi, b"plot1");
+            set_caller(accounts.alice);
+            assert_eq!(
+                split(&mut delphi, accounts.bob, b"", b"plot1b"),
+                Err(Error::EmptyPropertyId)
+            );
+            assert_eq!(
+                split(&mut delphi, accounts.bob, b"plot1a", b""),
+                Err(Error::EmptyPropertyId)
+            );
+            assert!(!delphi.property_exists(Vec::new()));
+        }
+
+        #[ink::test]
+        fn raw_account_info_decodes_back() {
+            let delphi = setup();
+            let accounts = accounts();
+
+            let raw = delphi.raw_account_info(accounts.bob).unwrap();
+            let info = <AccountInfo as scale::Decode>::decode(&mut &raw[..]).unwrap();
+            assert_eq!(info.name, b"bob".to_vec());
+            assert_eq!(info.timestamp, b"0".to_vec());
+            assert_eq!(delphi.raw_account_info(accounts.django), None);
+        }
+
+        #[ink::test]
+        fn mortgages_block_transfers_until_paid_off() {
+            let mut delphi = setup();
+            let accounts = accounts();
+            ink::env::test::set_account_balance::<DefaultEnvironment>(
+                ink::env::account_id::<DefaultEnvironment>(),
+                1_000,
+            );
+            let lenders_balance = || {
+                ink::env::test::get_account_balance::<DefaultEnvironment>(accounts.charlie).unwrap()
+            };
+            let balance_before = lenders_balance();
+
+            // only the owner can mortgage the property
+            set_caller(accounts.charlie);
+            assert_eq!(
+                delphi.create_mortgage(b"plot1".to_vec(), accounts.charlie, 10, CID.to_vec()),
+                Err(Error::NotPropertyOwner)
+            );
+
+            set_caller(accounts.alice);
+            delphi
+                .create_mortgage(b"plot1".to_vec(), accounts.charlie, 10, CID.to_vec())
+                .unwrap();
+            assert_eq!(
+                delphi.create_mortgage(b"plot1".to_vec(), accounts.bob, 10, CID.to_vec()),
+                Err(Error::PropertyEncumbered)
+            );
+            assert_eq!(
+                transfer(&mut delphi, accounts.bob),
+                Err(Error::PropertyEncumbered)
+            );
+
+            // a partial repayment goes to the lender and keeps the mortgage active
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(4);
+            delphi.repay_mortgage(b"plot1".to_vec()).unwrap();
+            let mortgage = delphi.mortgage_of(b"plot1".to_vec()).unwrap();
+            assert_eq!((mortgage.lender, mortgage.repaid), (accounts.charlie, 4));
+            assert_eq!(lenders_balance(), balance_before + 4);
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(0);
+            assert_eq!(
+                transfer(&mut delphi, accounts.bob),
+                Err(Error::PropertyEncumbered)
+            );
+
+            // paying off the rest releases the property
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(6);
+            delphi.repay_mortgage(b"plot1".to_vec()).unwrap();
+            assert!(delphi.mortgage_of(b"plot1".to_vec()).is_none());
+            assert_eq!(lenders_balance(), balance_before + 10);
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(0);
+            assert_eq!(transfer(&mut delphi, accounts.bob), Ok(()));
+            assert_eq!(
+                delphi.repay_mortgage(b"plot1".to_vec()),
+                Err(Error::NoMortgage)
+            );
+        }
+
+        #[ink::test]
+        fn empty_types_lists_only_types_without_claims() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.bob);
+            for property_type_id in [b"farm", b"lake"] {
+                register_type(&mut delphi, property_type_id, 1).unwrap();
+            }
+            assert_eq!(delphi.empty_types(), b"farm#lake#".to_vec());
+
+            set_caller(accounts.alice);
+            claim(&mut delphi, b"farm", b"farm1").unwrap();
+            assert_eq!(delphi.empty_types(), b"lake#".to_vec());
+        }
+
+        #[ink::test]
+        fn transfers_can_be_reversed_within_the_grace_period() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.bob);
+            delphi
+                .set_transfer_grace_blocks(b"land".to_vec(), 2)
+                .unwrap();
+
+            set_caller(accounts.alice);
+            transfer(&mut delphi, accounts.bob).unwrap();
+
+            // only the previous owner can reverse the transfer
+            set_caller(accounts.charlie);
+            assert_eq!(
+                delphi.reverse_transfer(b"plot1".to_vec()),
+                Err(Error::UnauthorizedAccount(accounts.charlie))
+            );
+
+            ink::env::test::advance_block::<DefaultEnvironment>();
+            set_caller(accounts.alice);
+            assert_eq!(delphi.reverse_transfer(b"plot1".to_vec()), Ok(()));
+            assert_eq!(delphi.claimer_of(b"plot1".to_vec()), Some(accounts.alice));
+            assert_eq!(delphi.transfer_history_len(b"plot1".to_vec()), 0);
+
+            // once the grace period lapses the transfer is final
+            transfer(&mut delphi, accounts.bob).unwrap();
+            for _ in 0..3 {
+                ink::env::test::advance_block::<DefaultEnvironment>();
+            }
+            assert_eq!(
+                delphi.reverse_transfer(b"plot1".to_vec()),
+                Err(Error::GracePeriodElapsed)
+            );
+            assert_eq!(delphi.claimer_of(b"plot1".to_vec()), Some(accounts.bob));
+        }
+
+        #[ink::test]
+        fn deregistered_types_leave_no_grace_period_behind() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.bob);
+            register_type(&mut delphi, b"farm", 1).unwrap();
+            delphi
+                .set_transfer_grace_blocks(b"farm".to_vec(), 5)
+                .unwrap();
+            delphi.deregister_ptype(b"farm".to_vec()).unwrap();
+            assert_eq!(delphi.transfer_grace_blocks(b"farm".to_vec()), 0);
+
+            // a new registrar of the id starts without it
+            set_caller(accounts.charlie);
+            register_type(&mut delphi, b"farm", 1).unwrap();
+            assert_eq!(delphi.transfer_grace_blocks(b"farm".to_vec()), 0);
+        }
+
+        #[ink::test]
+        fn disputed_or_encumbered_transfers_cannot_be_reversed() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.bob);
+            delphi
+                .set_transfer_grace_blocks(b"land".to_vec(), 2)
+                .unwrap();
+
+            set_caller(accounts.alice);
+            transfer(&mut delphi, accounts.bob).unwrap();
+
+            set_caller(accounts.charlie);
+            delphi
+                .raise_dispute(b"plot1".to_vec(), CID.to_vec())
+                .unwrap();
+            set_caller(accounts.alice);
+            assert_eq!(
+                delphi.reverse_transfer(b"plot1".to_vec()),
+                Err(Error::PropertyDisputed)
+            );
+
+            set_caller(accounts.bob);
+            delphi.resolve_dispute(b"plot1".to_vec()).unwrap();
+            delphi
+                .create_mortgage(b"plot1".to_vec(), accounts.charlie, 10, CID.to_vec())
+                .unwrap();
+            set_caller(accounts.alice);
+            assert_eq!(
+                delphi.reverse_transfer(b"plot1".to_vec()),
+                Err(Error::PropertyEncumbered)
+            );
+            assert_eq!(delphi.claimer_of(b"plot1".to_vec()), Some(accounts.bob));
+            assert_eq!(delphi.transfer_history_len(b"plot1".to_vec()), 1);
+        }
+
+        #[ink::test]
+        fn registrars_cannot_attest_their_own_claims() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.bob);
+            claim(&mut delphi, b"land", b"plot2").unwrap();
+            assert_eq!(
+                delphi.sign_document(b"plot2".to_vec(), b"land".to_vec(), b"1".to_vec()),
+                Err(Error::SelfAttestationForbidden)
+            );
+            assert!(!delphi.is_attested(b"plot2".to_vec()));
+        }
+
+        #[ink::test]
+        fn owner_and_originator_differ_after_a_transfer() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            assert_eq!(
+                delphi.owner_and_originator(b"plot1".to_vec()),
+                b"alice$alice".to_vec()
+            );
+
+            set_caller(accounts.alice);
+            transfer(&mut delphi, accounts.bob).unwrap();
+            assert_eq!(
+                delphi.owner_and_originator(b"plot1".to_vec()),
+                b"bob$alice".to_vec()
+            );
+        }
+
+        #[ink::test]
+        fn grant_easement_works() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                delphi.grant_easement(b"plot1".to_vec(), accounts.charlie, CID.to_vec()),
+                Err(Error::NotPropertyOwner)
+            );
+
+            set_caller(accounts.alice);
+            for beneficiary in [accounts.bob, accounts.charlie] {
+                delphi
+                    .grant_easement(b"plot1".to_vec(), beneficiary, CID.to_vec())
+                    .unwrap();
+            }
+            assert_eq!(
+                delphi.easements_of(b"plot1".to_vec()),
+                vec![
+                    (accounts.bob, CID.to_vec()),
+                    (accounts.charlie, CID.to_vec())
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn total_claims_follows_registrations_and_revocations() {
+            let mut delphi = setup();
+
+            set_caller(accounts().alice);
+            delphi
+                .register_claims_batch(
+                    b"land".to_vec(),
+                    vec![
+                        (b"plot2".to_vec(), CID.to_vec()),
+                        (b"plot3".to_vec(), CID.to_vec()),
+                    ],
+                    Vec::new(),
+                )
+                .unwrap();
+            assert_eq!(delphi.total_claims(), 3);
+
+            // a split withdraws the parcel and files its two parts
+            approve_subdivision(&mut delphi, b"plot1");
+            set_caller(accounts().alice);
+            split(&mut delphi, accounts().bob, b"plot1a", b"plot1b").unwrap();
+            assert_eq!(delphi.total_claims(), 4);
+
+            delphi.force_revoke_claim(b"plot2".to_vec()).unwrap();
+            assert_eq!(delphi.total_claims(), 3);
+            assert_eq!(delphi.total_properties(), 3);
+        }
+
+        #[ink::test]
+        fn type_ids_are_unique_across_registrars() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                register_type(&mut delphi, b"land", 1),
+                Err(Error::TypeAlreadyRegistered)
+            );
+            assert!(delphi.registrations_of(accounts.charlie).is_empty());
+            assert_eq!(
+                delphi.sign_document(b"plot1".to_vec(), b"land".to_vec(), b"1".to_vec()),
+                Err(Error::UnauthorizedAccount(accounts.charlie))
+            );
+        }
+
+        #[ink::test]
+        fn mark_digitized_works() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            assert_eq!(delphi.digitized_by(b"plot1".to_vec()), None);
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                delphi.mark_digitized(b"plot1".to_vec()),
+                Err(Error::UnauthorizedAccount(accounts.charlie))
+            );
+
+            set_caller(accounts.bob);
+            delphi.mark_digitized(b"plot1".to_vec()).unwrap();
+            assert_eq!(delphi.digitized_by(b"plot1".to_vec()), Some(accounts.bob));
+        }
+
+        #[ink::test]
+        fn stale_claims_lists_old_unattested_claims() {
+            let mut delphi = setup();
+
+            for _ in 0..5 {
+                ink::env::test::advance_block::<DefaultEnvironment>();
+            }
+            set_caller(accounts().alice);
+            claim(&mut delphi, b"land", b"plot2").unwrap();
+
+            assert_eq!(delphi.stale_claims(b"land".to_vec(), 3), b"plot1#".to_vec());
+            assert!(delphi.stale_claims(b"land".to_vec(), 10).is_empty());
+
+            // attested claims are never stale
+            set_caller(accounts().bob);
+            delphi
+                .sign_document(b"plot1".to_vec(), b"land".to_vec(), b"1".to_vec())
+                .unwrap();
+            assert!(delphi.stale_claims(b"land".to_vec(), 3).is_empty());
+        }
+
+        #[ink::test]
+        fn sale_contracts_are_recorded_with_each_sale() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            for (seller, buyer, contract_hash) in [
+                (accounts.alice, accounts.bob, [1u8; 32]),
+                (accounts.bob, accounts.charlie, [2u8; 32]),
+            ] {
+                set_caller(seller);
+                delphi
+                    .transfer_whole(
+                        b"plot1".to_vec(),
+                        buyer,
+                        CID.to_vec(),
+                        b"1".to_vec(),
+                        contract_hash,
+                        Vec::new(),
+                    )
+                    .unwrap();
+            }
+
+            assert_eq!(
+                delphi.sale_contract_of(b"plot1".to_vec(), 0),
+                Some([1u8; 32])
+            );
+            assert_eq!(
+                delphi.sale_contract_of(b"plot1".to_vec(), 1),
+                Some([2u8; 32])
+            );
+            assert_eq!(delphi.sale_contract_of(b"plot1".to_vec(), 2), None);
+        }
+
+        #[ink::test]
+        fn distinct_attesters_counts_each_authority_once() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.bob);
+            register_type(&mut delphi, b"farm", 3).unwrap();
+            delphi
+                .add_signer(b"farm".to_vec(), accounts.django)
+                .unwrap();
+            set_caller(accounts.alice);
+            claim(&mut delphi, b"farm", b"farm1").unwrap();
+            assert_eq!(delphi.distinct_attesters(b"farm1".to_vec()), 0);
+
+            for signer in [accounts.bob, accounts.bob, accounts.django] {
+                set_caller(signer);
+                delphi
+                    .sign_document(b"farm1".to_vec(), b"farm".to_vec(), b"1".to_vec())
+                    .unwrap();
+            }
+            assert_eq!(delphi.distinct_attesters(b"farm1".to_vec()), 2);
+            assert!(!delphi.is_attested(b"farm1".to_vec()));
+        }
+
+        #[ink::test]
+        fn full_attestation_is_announced_once() {
+            type Event = <Delphi as ink::reflect::ContractEventBase>::Type;
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.bob);
+            register_type(&mut delphi, b"farm", 2).unwrap();
+            delphi
+                .add_signer(b"farm".to_vec(), accounts.django)
+                .unwrap();
+            set_caller(accounts.alice);
+            claim(&mut delphi, b"farm", b"farm1").unwrap();
+
+            // signing needs an assertion timestamp
+            set_caller(accounts.bob);
+            assert_eq!(
+                delphi.sign_document(b"farm1".to_vec(), b"farm".to_vec(), Vec::new()),
+                Err(Error::EmptyAssertionTimestamp)
+            );
+
+            // signing again after the threshold is met does not announce the property again
+            let events = ink::env::test::recorded_events().count();
+            for signer in [accounts.bob, accounts.django, accounts.bob] {
+                set_caller(signer);
+                delphi
+                    .sign_document(b"farm1".to_vec(), b"farm".to_vec(), b"1".to_vec())
+                    .unwrap();
+            }
+            let fully_attested = ink::env::test::recorded_events()
+                .skip(events)
+                .filter(|event| {
+                    matches!(
+                        <Event as scale::Decode>::decode(&mut &event.data[..]),
+                        Ok(Event::PropertyFullyAttested(_))
+                    )
+                })
+                .count();
+            assert_eq!(fully_attested, 1);
+            assert!(delphi.is_attested(b"farm1".to_vec()));
+        }
+
+        #[ink::test]
+        fn withdraw_fees_keeps_the_fee_accounting() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.alice);
+            delphi.set_ptype_fee(10).unwrap();
+            set_caller(accounts.charlie);
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(15);
+            register_type(&mut delphi, b"farm", 1).unwrap();
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(0);
+
+            // a refused withdrawal leaves the fees on record
+            assert_eq!(delphi.withdraw_fees(accounts.charlie), Err(Error::NotOwner));
+            assert_eq!(delphi.collected_fees(), 15);
+
+            // so does a failed transfer, simulated by a contract account the engine holds no balance for
+            let contract = ink::env::account_id::<DefaultEnvironment>();
+            ink::env::test::set_callee::<DefaultEnvironment>(AccountId::from([0x42; 32]));
+            set_caller(accounts.alice);
+            assert_eq!(
+                delphi.withdraw_fees(accounts.django),
+                Err(Error::TransferFailed)
+            );
+            assert_eq!(delphi.collected_fees(), 15);
+            ink::env::test::set_callee::<DefaultEnvironment>(contract);
+
+            ink::env::test::set_account_balance::<DefaultEnvironment>(contract, 100);
+            let balance_before =
+                ink::env::test::get_account_balance::<DefaultEnvironment>(accounts.django).unwrap();
+            assert_eq!(delphi.withdraw_fees(accounts.django), Ok(()));
+            assert_eq!(delphi.collected_fees(), 0);
+            assert_eq!(
+                ink::env::test::get_account_balance::<DefaultEnvironment>(accounts.django),
+                Ok(balance_before + 15)
+            );
+        }
+
+        #[ink::test]
+        fn import_property_works() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            let import = |delphi: &mut Delphi, external_id: &[u8], property_id: &[u8]| {
+                delphi.import_property(
+                    external_id.to_vec(),
+                    b"land".to_vec(),
+                    property_id.to_vec(),
+                    accounts.charlie,
+                    CID.to_vec(),
+                )
+            };
+
+            // only the contract owner can import records
+            set_caller(accounts.bob);
+            assert_eq!(
+                import(&mut delphi, b"old-1", b"plot2"),
+                Err(Error::NotOwner)
+            );
+
+            set_caller(accounts.alice);
+            assert_eq!(import(&mut delphi, b"old-1", b"plot2"), Ok(()));
+            assert_eq!(delphi.claimer_of(b"plot2".to_vec()), Some(accounts.charlie));
+            assert_eq!(delphi.external_id_of(b"plot2".to_vec()), b"old-1".to_vec());
+            assert_eq!(delphi.digitized_by(b"plot2".to_vec()), Some(accounts.alice));
+
+            // each external record can only be imported once, and never over an existing property
+            assert_eq!(
+                import(&mut delphi, b"old-1", b"plot3"),
+                Err(Error::DuplicateExternalId)
+            );
+            assert_eq!(
+                import(&mut delphi, b"old-2", b"plot1"),
+                Err(Error::PropertyAlreadyExists(b"plot1".to_vec()))
+            );
+            assert!(!delphi.property_exists(b"plot3".to_vec()));
+            assert_eq!(delphi.claimer_of(b"plot1".to_vec()), Some(accounts.alice));
+        }
+
+        #[ink::test]
+        fn authority_leaderboard_ranks_by_attestations() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.django);
+            delphi
+                .register_account(b"django".to_vec(), b"django".to_vec(), b"0".to_vec())
+                .unwrap();
+            set_caller(accounts.bob);
+            delphi
+                .add_signer(b"land".to_vec(), accounts.django)
+                .unwrap();
+
+            set_caller(accounts.alice);
+            delphi
+                .register_claims_batch(
+                    b"land".to_vec(),
+                    vec![
+                        (b"plot2".to_vec(), CID.to_vec()),
+                        (b"plot3".to_vec(), CID.to_vec()),
+                    ],
+                    Vec::new(),
+                )
+                .unwrap();
+
+            for (signer, property_id) in [
+                (accounts.bob, b"plot1"),
+                (accounts.django, b"plot2"),
+                (accounts.django, b"plot3"),
+            ] {
+                set_caller(signer);
+                delphi
+                    .sign_document(property_id.to_vec(), b"land".to_vec(), b"1".to_vec())
+                    .unwrap();
+            }
+
+            assert_eq!(
+                delphi.authority_leaderboard(b"land".to_vec()),
+                b"django~2###bob~1###".to_vec()
+            );
+        }
+
+        #[ink::test]
+        fn only_the_owner_can_set_code() {
+            let mut delphi = setup();
+
+            set_caller(accounts().bob);
+            assert_eq!(delphi.set_code(Hash::from([1u8; 32])), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn error_payloads_decode_back() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.charlie);
+            for (result, expected) in [
+                (
+                    delphi.approve_subdivision(b"plot9".to_vec(), CID.to_vec()),
+                    Error::PropertyNotFound(b"plot9".to_vec()),
+                ),
+                (
+                    delphi.approve_subdivision(b"plot1".to_vec(), CID.to_vec()),
+                    Error::UnauthorizedAccount(accounts.charlie),
+                ),
+            ] {
+                let encoded = scale::Encode::encode(&result.unwrap_err());
+                assert_eq!(
+                    <Error as scale::Decode>::decode(&mut &encoded[..]),
+                    Ok(expected)
+                );
+            }
+        }
+
+        #[ink::test]
+        fn property_meta_reflects_the_stored_state() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            assert!(delphi.property_meta(b"plot9".to_vec()).is_none());
+            let claimed_at = ink::env::block_number::<DefaultEnvironment>();
+
+            // a transfer, a government charge and a dispute
+            ink::env::test::advance_block::<DefaultEnvironment>();
+            set_caller(accounts.alice);
+            transfer(&mut delphi, accounts.charlie).unwrap();
+            set_caller(accounts.bob);
+            delphi
+                .add_government_charge(b"plot1".to_vec(), b"rates-2024".to_vec(), 50)
+                .unwrap();
+            set_caller(accounts.alice);
+            delphi
+                .raise_dispute(b"plot1".to_vec(), CID.to_vec())
+                .unwrap();
+            let meta = delphi.property_meta(b"plot1".to_vec()).unwrap();
+            assert!(meta.disputed && meta.encumbered && !meta.attested);
+            assert_eq!((meta.transfer_count, meta.charge_count), (1, 1));
+            assert_eq!(meta.claimed_at, claimed_at);
+            assert_eq!(meta.last_modified_block, claimed_at + 1);
+
+            // resolving the dispute, clearing the charge and attesting flip every flag and touch the record
+            ink::env::test::advance_block::<DefaultEnvironment>();
+            set_caller(accounts.bob);
+            delphi.resolve_dispute(b"plot1".to_vec()).unwrap();
+            delphi
+                .clear_government_charge(b"plot1".to_vec(), b"rates-2024".to_vec())
+                .unwrap();
+            delphi
+                .sign_document(b"plot1".to_vec(), b"land".to_vec(), b"1".to_vec())
+                .unwrap();
+            let meta = delphi.property_meta(b"plot1".to_vec()).unwrap();
+            assert!(!meta.disputed && !meta.encumbered && meta.attested);
+            assert_eq!((meta.transfer_count, meta.charge_count), (1, 0));
+            assert_eq!(meta.claimed_at, claimed_at);
+            assert_eq!(meta.last_modified_block, claimed_at + 2);
+        }
+
+        #[ink::test]
+        fn account_names_must_have_a_valid_length() {
+            let mut delphi = setup();
+
+            set_caller(accounts().django);
+            for name in [Vec::new(), vec![b'a'; MAX_NAME_LENGTH + 1]] {
+                assert_eq!(
+                    delphi.register_account(b"django".to_vec(), name, b"0".to_vec()),
+                    Err(Error::InvalidNameLength)
+                );
+            }
+            assert!(!delphi.account_exists().0);
+            assert_eq!(
+                delphi.register_account(
+                    b"django".to_vec(),
+                    vec![b'a'; MAX_NAME_LENGTH],
+                    b"0".to_vec()
+                ),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn claims_under_unknown_types_are_rejected() {
+            let mut delphi = setup();
+
+            set_caller(accounts().alice);
+            assert_eq!(
+                claim(&mut delphi, b"bogus", b"plot2"),
+                Err(Error::UnknownPropertyType)
+            );
+            assert!(!delphi.property_exists(b"plot2".to_vec()));
+            assert!(delphi.property_claims(b"bogus".to_vec()).is_empty());
+        }
+
+        #[ink::test]
+        fn transfers_to_unregistered_accounts_are_rejected() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.alice);
+            assert_eq!(
+                transfer(&mut delphi, accounts.django),
+                Err(Error::RecipientNotRegistered)
+            );
+            assert_eq!(delphi.claimer_of(b"plot1".to_vec()), Some(accounts.alice));
+        }
+
+        #[ink::test]
+        fn registrars_cannot_sign_properties_of_other_types() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.charlie);
+            register_type(&mut delphi, b"farm", 1).unwrap();
+            assert_eq!(
+                delphi.sign_document(b"plot1".to_vec(), b"farm".to_vec(), b"1".to_vec()),
+                Err(Error::TypeMismatch)
+            );
+            assert!(!delphi.is_attested(b"plot1".to_vec()));
+        }
+
+        #[ink::test]
+        fn remove_account_works() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.alice);
+            assert_eq!(delphi.remove_account(), Err(Error::AccountHasProperties));
+            transfer(&mut delphi, accounts.bob).unwrap();
+
+            assert_eq!(delphi.remove_account(), Ok(()));
+            assert!(!delphi.account_exists().0);
+            assert_eq!(delphi.total_accounts(), 2);
+            assert_eq!(delphi.remove_account(), Err(Error::AccountNotFound));
+            assert!(!delphi.account_ids.contains(accounts.alice));
+        }
+
+        #[ink::test]
+        fn claims_cannot_overwrite_existing_properties() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                claim(&mut delphi, b"land", b"plot1"),
+                Err(Error::PropertyAlreadyExists(b"plot1".to_vec()))
+            );
+            assert_eq!(
+                delphi.register_claims_batch(
+                    b"land".to_vec(),
+                    vec![
+                        (b"plot2".to_vec(), CID.to_vec()),
+                        (b"plot2".to_vec(), CID.to_vec())
+                    ],
+                    Vec::new()
+                ),
+                Err(Error::PropertyAlreadyExists(b"plot2".to_vec()))
+            );
+            assert_eq!(delphi.claimer_of(b"plot1".to_vec()), Some(accounts.alice));
+        }
+
+        #[ink::test]
+        fn disputes_survive_reclaims_and_freeze_the_property() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.charlie);
+            delphi
+                .raise_dispute(b"plot1".to_vec(), CID.to_vec())
+                .unwrap();
+
+            // the claimer cannot clear the flag by claiming the property again
+            set_caller(accounts.alice);
+            assert_eq!(
+                claim(&mut delphi, b"land", b"plot1"),
+                Err(Error::PropertyAlreadyExists(b"plot1".to_vec()))
+            );
+            assert!(delphi.property_meta(b"plot1".to_vec()).unwrap().disputed);
+            assert_eq!(
+                transfer(&mut delphi, accounts.bob),
+                Err(Error::PropertyDisputed)
+            );
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                delphi.sign_document(b"plot1".to_vec(), b"land".to_vec(), b"1".to_vec()),
+                Err(Error::PropertyDisputed)
+            );
+
+            // only the registrar of the type can resolve the dispute
+            set_caller(accounts.charlie);
+            assert_eq!(
+                delphi.resolve_dispute(b"plot1".to_vec()),
+                Err(Error::UnauthorizedAccount(accounts.charlie))
+            );
+            set_caller(accounts.bob);
+            delphi.resolve_dispute(b"plot1".to_vec()).unwrap();
+            assert_eq!(
+                delphi.sign_document(b"plot1".to_vec(), b"land".to_vec(), b"1".to_vec()),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn splits_cannot_overwrite_existing_properties() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.charlie);
+            claim(&mut delphi, b"land", b"plot2").unwrap();
+            approve_subdivision(&mut delphi, b"plot1");
+
+            set_caller(accounts.alice);
+            assert_eq!(
+                split(&mut delphi, accounts.bob, b"plot1a", b"plot2"),
+                Err(Error::PropertyAlreadyExists(b"plot2".to_vec()))
+            );
+            assert_eq!(
+                split(&mut delphi, accounts.bob, b"plot1a", b"plot1a"),
+                Err(Error::PropertyAlreadyExists(b"plot1a".to_vec()))
+            );
+            assert_eq!(delphi.claimer_of(b"plot2".to_vec()), Some(accounts.charlie));
+
+            // the id of the property being split can be kept by one of the parts
+            assert_eq!(
+                split(&mut delphi, accounts.bob, b"plot1", b"plot1b"),
+                Ok(())
+            );
+            assert_eq!(delphi.claimer_of(b"plot1".to_vec()), Some(accounts.alice));
+            assert_eq!(delphi.claimer_of(b"plot1b".to_vec()), Some(accounts.bob));
+            assert_eq!(delphi.total_properties(), 3);
+        }
+
+        #[ink::test]
+        fn reclassified_properties_need_a_new_subdivision_approval() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.charlie);
+            register_type(&mut delphi, b"farm", 1).unwrap();
+            approve_subdivision(&mut delphi, b"plot1");
+
+            // the plan was approved by an authority of the old type
+            set_caller(accounts.alice);
+            delphi
+                .reclassify_property(b"plot1".to_vec(), b"farm".to_vec())
+                .unwrap();
+            assert_eq!(
+                split(&mut delphi, accounts.bob, b"plot1a", b"plot1b"),
+                Err(Error::SubdivisionNotApproved)
+            );
+            assert!(delphi.property_exists(b"plot1".to_vec()));
+        }
+
+        #[ink::test]
+        fn split_ids_leave_nothing_behind() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.alice);
+            delphi
+                .add_policy(b"plot1".to_vec(), CID.to_vec(), 100)
+                .unwrap();
+            delphi
+                .grant_easement(b"plot1".to_vec(), accounts.charlie, CID.to_vec())
+                .unwrap();
+            set_caller(accounts.bob);
+            delphi
+                .set_map_ref(b"plot1".to_vec(), b"sheet-4".to_vec())
+                .unwrap();
+            set_caller(accounts.charlie);
+            delphi
+                .peer_review(b"plot1".to_vec(), true, CID.to_vec())
+                .unwrap();
+
+            // the part that keeps the id of the split property does not inherit its records
+            approve_subdivision(&mut delphi, b"plot1");
+            set_caller(accounts.alice);
+            split(&mut delphi, accounts.bob, b"plot1", b"plot1b").unwrap();
+            assert!(delphi.active_policies(b"plot1".to_vec()).is_empty());
+            assert!(delphi.easements_of(b"plot1".to_vec()).is_empty());
+            assert!(delphi.map_ref_of(b"plot1".to_vec()).is_empty());
+            assert!(delphi.peer_reviews(b"plot1".to_vec()).is_empty());
+        }
+
+        #[ink::test]
+        fn merges_cannot_overwrite_existing_properties() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            claim(&mut delphi, b"land", b"plot2").unwrap();
+            set_caller(accounts.charlie);
+            claim(&mut delphi, b"land", b"plot3").unwrap();
+
+            set_caller(accounts.alice);
+            let merge = |delphi: &mut Delphi, merged_property_id: &[u8]| {
+                delphi.merge_properties(
+                    vec![b"plot1".to_vec(), b"plot2".to_vec()],
+                    merged_property_id.to_vec(),
+                    CID.to_vec(),
+                    b"1".to_vec(),
+                )
+            };
+            assert_eq!(
+                merge(&mut delphi, b"plot3"),
+                Err(Error::PropertyAlreadyExists(b"plot3".to_vec()))
+            );
+            assert_eq!(delphi.claimer_of(b"plot3".to_vec()), Some(accounts.charlie));
+
+            // the id of one of the merged properties can be kept
+            assert_eq!(merge(&mut delphi, b"plot2"), Ok(()));
+            assert!(!delphi.property_exists(b"plot1".to_vec()));
+            assert_eq!(delphi.claimer_of(b"plot2".to_vec()), Some(accounts.alice));
+            assert_eq!(delphi.transfer_history_len(b"plot2".to_vec()), 1);
+            assert_eq!(delphi.total_properties(), 2);
+        }
+
+        #[ink::test]
+        fn merged_ids_leave_nothing_behind() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            // plot1 gathers records of every kind and plot2 is migrated from an external registry
+            set_caller(accounts.alice);
+            delphi
+                .add_policy(b"plot1".to_vec(), CID.to_vec(), 100)
+                .unwrap();
+            delphi
+                .grant_easement(b"plot1".to_vec(), accounts.charlie, CID.to_vec())
+                .unwrap();
+            delphi
+                .import_property(
+                    b"old-1".to_vec(),
+                    b"land".to_vec(),
+                    b"plot2".to_vec(),
+                    accounts.alice,
+                    CID.to_vec(),
+                )
+                .unwrap();
+            set_caller(accounts.bob);
+            delphi
+                .set_map_ref(b"plot1".to_vec(), b"sheet-4".to_vec())
+                .unwrap();
+            set_caller(accounts.charlie);
+            delphi
+                .peer_review(b"plot1".to_vec(), true, CID.to_vec())
+                .unwrap();
+
+            set_caller(accounts.alice);
+            delphi
+                .merge_properties(
+                    vec![b"plot1".to_vec(), b"plot2".to_vec()],
+                    b"plot3".to_vec(),
+                    CID.to_vec(),
+                    b"1".to_vec(),
+                )
+                .unwrap();
+
+            // a later claim to a merged id starts from a clean slate
+            set_caller(accounts.charlie);
+            for property_id in [b"plot1", b"plot2"] {
+                claim(&mut delphi, b"land", property_id).unwrap();
+                assert!(delphi.active_policies(property_id.to_vec()).is_empty());
+                assert!(delphi.easements_of(property_id.to_vec()).is_empty());
+                assert!(delphi.map_ref_of(property_id.to_vec()).is_empty());
+                assert!(delphi.peer_reviews(property_id.to_vec()).is_empty());
+                assert!(delphi.external_id_of(property_id.to_vec()).is_empty());
+            }
+
+            // and the external record can be imported again
+            set_caller(accounts.alice);
+            assert_eq!(
+                delphi.import_property(
+                    b"old-1".to_vec(),
+                    b"land".to_vec(),
+                    b"plot4".to_vec(),
+                    accounts.alice,
+                    CID.to_vec(),
+                ),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn derived_ids_can_only_be_claimed_once() {
+            let mut delphi = setup();
+
+            let property_id = delphi
+                .register_claim_auto(b"land".to_vec(), 7, CID.to_vec(), Vec::new())
+                .unwrap();
+            assert!(delphi.property_exists(property_id.clone()));
+            assert_eq!(
+                delphi.register_claim_auto(b"land".to_vec(), 7, CID.to_vec(), Vec::new()),
+                Err(Error::PropertyAlreadyExists(property_id))
+            );
+            assert_eq!(delphi.total_properties(), 2);
+        }
+
+        #[ink::test]
+        fn attested_index_follows_signatures() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            // signing again does not index the property twice
+            set_caller(accounts.bob);
+            for _ in 0..2 {
+                delphi
+                    .sign_document(b"plot1".to_vec(), b"land".to_vec(), b"1".to_vec())
+                    .unwrap();
+            }
+            assert_eq!(
+                delphi.properties_attested_by(accounts.bob),
+                vec![b"plot1".to_vec()]
+            );
+
+            // the split removes the signed property
+            approve_subdivision(&mut delphi, b"plot1");
+            set_caller(accounts.alice);
+            split(&mut delphi, accounts.bob, b"plot1a", b"plot1b").unwrap();
+            assert!(delphi.properties_attested_by(accounts.bob).is_empty());
+
+            // a new claim document clears the signatures gathered so far
+            set_caller(accounts.bob);
+            register_type(&mut delphi, b"farm", 2).unwrap();
+            set_caller(accounts.alice);
+            claim(&mut delphi, b"farm", b"farm1").unwrap();
+            set_caller(accounts.bob);
+            delphi
+                .sign_document(b"farm1".to_vec(), b"farm".to_vec(), b"1".to_vec())
+                .unwrap();
+            assert_eq!(
+                delphi.properties_attested_by(accounts.bob),
+                vec![b"farm1".to_vec()]
+            );
+            set_caller(accounts.alice);
+            delphi
+                .update_claim_address(b"farm1".to_vec(), CID.to_vec())
+                .unwrap();
+            assert!(delphi.properties_attested_by(accounts.bob).is_empty());
+        }
+
+        #[ink::test]
+        fn force_revoke_claim_removes_everything_recorded_against_the_property() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.alice);
+            delphi
+                .import_property(
+                    b"old-1".to_vec(),
+                    b"land".to_vec(),
+                    b"plot2".to_vec(),
+                    accounts.alice,
+                    CID.to_vec(),
+                )
+                .unwrap();
+            delphi
+                .add_policy(b"plot2".to_vec(), CID.to_vec(), 100)
+                .unwrap();
+            delphi
+                .grant_easement(b"plot2".to_vec(), accounts.charlie, CID.to_vec())
+                .unwrap();
+            delphi
+                .create_mortgage(b"plot2".to_vec(), accounts.charlie, 10, CID.to_vec())
+                .unwrap();
+
+            set_caller(accounts.bob);
+            delphi
+                .sign_document(b"plot2".to_vec(), b"land".to_vec(), b"1".to_vec())
+                .unwrap();
+            delphi
+                .set_map_ref(b"plot2".to_vec(), b"sheet-4".to_vec())
+                .unwrap();
+            delphi
+                .add_government_charge(b"plot2".to_vec(), b"rates".to_vec(), 5)
+                .unwrap();
+
+            set_caller(accounts.charlie);
+            delphi
+                .peer_review(b"plot2".to_vec(), true, CID.to_vec())
+                .unwrap();
+            delphi
+                .raise_dispute(b"plot2".to_vec(), CID.to_vec())
+                .unwrap();
+
+            // only the contract owner can revoke
+            assert_eq!(
+                delphi.force_revoke_claim(b"plot2".to_vec()),
+                Err(Error::NotOwner)
+            );
+            set_caller(accounts.alice);
+            delphi.force_revoke_claim(b"plot2".to_vec()).unwrap();
+
+            let property_id = b"plot2".to_vec();
+            assert!(!delphi.property_exists(property_id.clone()));
+            assert!(delphi.mortgage_of(property_id.clone()).is_none());
+            assert!(delphi.government_charges(property_id.clone()).is_empty());
+            assert!(!delphi.disputes.contains(&property_id));
+            assert!(delphi.active_policies(property_id.clone()).is_empty());
+            assert!(delphi.easements_of(property_id.clone()).is_empty());
+            assert!(delphi.map_ref_of(property_id.clone()).is_empty());
+            assert!(delphi.peer_reviews(property_id.clone()).is_empty());
+            assert!(delphi.external_id_of(property_id).is_empty());
+            assert!(delphi.properties_attested_by(accounts.bob).is_empty());
+
+            // the external record can be imported again
+            assert_eq!(
+                delphi.import_property(
+                    b"old-1".to_vec(),
+                    b"land".to_vec(),
+                    b"plot3".to_vec(),
+                    accounts.alice,
+                    CID.to_vec(),
+                ),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn sign_documents_batch_rejects_missing_properties() {
+            let mut delphi = setup();
+            let accounts = accounts();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                delphi.sign_document(b"plot9".to_vec(), b"land".to_vec(), b"1".to_vec()),
+                Err(Error::PropertyNotFound(b"plot9".to_vec()))
+            );
+            assert_eq!(
+                delphi.sign_documents_batch(
+                    vec![
+                        (b"plot1".to_vec(), b"land".to_vec()),
+                        (b"plot9".to_vec(), b"land".to_vec())
+                    ],
+                    b"1".to_vec()
+                ),
+                Err(Error::PropertyNotFound(b"plot9".to_vec()))
+            );
         }
     }
 }